@@ -2,17 +2,66 @@ use axum::{routing::post, Router};
 use std::net::SocketAddr;
 
 mod routes;
-use routes::token::{create_token, mint_token};
 #[tokio::main]
 async fn main() {
     let app = Router::new()
         .route("/keypair", post(routes::keypair::generate_keypair))
+        .route("/keypair/derive", post(routes::keypair::derive_keypair))
+        .route("/keypair/grind", post(routes::keypair::grind_keypair))
         .route("/token/create", post(routes::token::create_token))
         .route("/token/mint", post(routes::token::mint_token))
+        .route("/token/initialize-account", post(routes::token::initialize_account))
+        .route("/token/burn", post(routes::token::burn_token))
+        .route("/token/approve", post(routes::token::approve_delegate))
+        .route("/token/revoke", post(routes::token::revoke_delegate))
+        .route("/token/set-authority", post(routes::token::set_authority))
+        .route("/token/freeze", post(routes::token::freeze_account))
+        .route("/token/thaw", post(routes::token::thaw_account))
+        .route("/token/close", post(routes::token::close_account))
+        .route("/token/sync-native", post(routes::token::sync_native))
+        .route("/token/multisig", post(routes::token::initialize_multisig))
+        .route("/token/wrap", post(routes::token::wrap_sol))
+        .route("/token/unwrap", post(routes::token::unwrap_sol))
+        .route("/token/metadata/create", post(routes::metadata::create_metadata))
+        .route("/token/metadata/update", post(routes::metadata::update_metadata))
         .route("/message/sign", post(routes::message::sign_message))
         .route("/message/verify", post(routes::message::verify_message))
+        .route("/message/ed25519-instruction", post(routes::message::ed25519_instruction))
         .route("/send/sol", post(routes::transfer::send_sol))
-        .route("/send/token", post(routes::transfer::send_token));
+        .route("/send/token", post(routes::transfer::send_token))
+        .route("/send/batch", post(routes::transfer::send_batch))
+        .route("/system/create-account", post(routes::transfer::create_account))
+        .route("/system/create-account-with-seed", post(routes::transfer::create_account_with_seed))
+        .route("/system/allocate", post(routes::transfer::allocate))
+        .route("/system/assign", post(routes::transfer::assign))
+        .route("/system/transfer-with-seed", post(routes::transfer::transfer_with_seed))
+        .route("/system/transfer-many", post(routes::transfer::transfer_many))
+        .route("/nonce/create", post(routes::nonce::create_nonce_account))
+        .route("/nonce/advance", post(routes::nonce::advance_nonce_account))
+        .route("/nonce/withdraw", post(routes::nonce::withdraw_nonce_account))
+        .route("/nonce/authorize", post(routes::nonce::authorize_nonce_account))
+        .route("/stake/create", post(routes::stake::create_stake_account))
+        .route("/stake/delegate", post(routes::stake::delegate_stake))
+        .route("/stake/deactivate", post(routes::stake::deactivate_stake))
+        .route("/stake/withdraw", post(routes::stake::withdraw_stake))
+        .route("/stake/split", post(routes::stake::split_stake))
+        .route("/stake/merge", post(routes::stake::merge_stake))
+        .route("/stake/authorize", post(routes::stake::authorize_stake))
+        .route("/lookup-table/create", post(routes::lookup_table::create_lookup_table))
+        .route("/lookup-table/extend", post(routes::lookup_table::extend_lookup_table))
+        .route("/lookup-table/freeze", post(routes::lookup_table::freeze_lookup_table))
+        .route("/lookup-table/deactivate", post(routes::lookup_table::deactivate_lookup_table))
+        .route("/lookup-table/close", post(routes::lookup_table::close_lookup_table))
+        .route("/address/pda", post(routes::address::derive_pda))
+        .route("/address/with-seed", post(routes::address::derive_with_seed))
+        .route("/address/ata", post(routes::address::derive_ata))
+        .route("/transaction/build", post(routes::transaction::build_transaction))
+        .route("/transaction/sign", post(routes::message::sign_transaction))
+        .route("/transaction/decode", post(routes::transaction::decode_transaction))
+        .route("/decode/mint", post(routes::decode::decode_mint))
+        .route("/decode/token-account", post(routes::decode::decode_token_account))
+        .route("/compute-budget", post(routes::compute_budget::build_compute_budget))
+        .route("/memo", post(routes::memo::build_memo));
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
     println!("Listening on {}", addr);
@@ -0,0 +1,263 @@
+use axum::{Json, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use bs58::decode as bs58_decode;
+use axum::http::StatusCode;
+use serde_json::json;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum SeedInput {
+    Utf8(String),
+    Hex(String),
+    Base58(String),
+    Pubkey(String),
+}
+
+#[derive(Deserialize)]
+pub struct PdaRequest {
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    pub seeds: Vec<SeedInput>,
+}
+
+#[derive(Serialize)]
+pub struct PdaResponse {
+    pub address: String,
+    pub bump: u8,
+}
+
+#[derive(Deserialize)]
+pub struct WithSeedRequest {
+    pub base: String,
+    pub seed: String,
+    #[serde(rename = "programId")]
+    pub program_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct AtaRequest {
+    pub wallet: String,
+    pub mint: String,
+    // Defaults to the SPL Token program
+    #[serde(rename = "tokenProgram")]
+    pub token_program: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AddressResponse {
+    pub address: String,
+}
+
+pub async fn derive_pda(Json(payload): Json<PdaRequest>) -> impl IntoResponse {
+    // Parse program id
+    let program_id = match payload.program_id.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `programId` address"
+                }))
+            );
+        }
+    };
+
+    // The bump seed takes the last of the MAX_SEEDS slots
+    if payload.seeds.len() >= MAX_SEEDS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": format!("At most {} seeds are allowed", MAX_SEEDS - 1)
+            }))
+        );
+    }
+
+    // Decode each seed into raw bytes
+    let mut seeds = Vec::with_capacity(payload.seeds.len());
+    for (i, seed) in payload.seeds.iter().enumerate() {
+        let bytes = match decode_seed(seed) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Invalid seed {}: {}", i, e)
+                    }))
+                );
+            }
+        };
+        if bytes.len() > MAX_SEED_LEN {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Invalid seed {}: longer than {} bytes", i, MAX_SEED_LEN)
+                }))
+            );
+        }
+        seeds.push(bytes);
+    }
+
+    let seed_slices = seeds.iter().map(|seed| seed.as_slice()).collect::<Vec<_>>();
+    let (address, bump) = match Pubkey::try_find_program_address(&seed_slices, &program_id) {
+        Some(found) => found,
+        None => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "success": false,
+                    "error": "Unable to find a viable program address bump seed"
+                }))
+            );
+        }
+    };
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": PdaResponse {
+            address: address.to_string(),
+            bump,
+        }
+    })))
+}
+
+pub async fn derive_with_seed(Json(payload): Json<WithSeedRequest>) -> impl IntoResponse {
+    // Parse base public key
+    let base = match payload.base.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `base` address"
+                }))
+            );
+        }
+    };
+
+    // Parse owning program id
+    let program_id = match payload.program_id.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `programId` address"
+                }))
+            );
+        }
+    };
+
+    let address = match Pubkey::create_with_seed(&base, &payload.seed, &program_id) {
+        Ok(address) => address,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Failed to derive address with seed: {}", e)
+                }))
+            );
+        }
+    };
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": AddressResponse {
+            address: address.to_string(),
+        }
+    })))
+}
+
+pub async fn derive_ata(Json(payload): Json<AtaRequest>) -> impl IntoResponse {
+    // Parse wallet public key
+    let wallet = match payload.wallet.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `wallet` address"
+                }))
+            );
+        }
+    };
+
+    // Parse mint public key
+    let mint = match payload.mint.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `mint` address"
+                }))
+            );
+        }
+    };
+
+    // Parse optional token program id
+    let token_program = match payload.token_program.as_deref() {
+        None => spl_token::id(),
+        Some(program) => match program.parse::<Pubkey>() {
+            Ok(pk) => pk,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": "Invalid `tokenProgram` address"
+                    }))
+                );
+            }
+        },
+    };
+
+    let address = get_associated_token_address_with_program_id(&wallet, &mint, &token_program);
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": AddressResponse {
+            address: address.to_string(),
+        }
+    })))
+}
+
+fn decode_seed(seed: &SeedInput) -> Result<Vec<u8>, String> {
+    match seed {
+        SeedInput::Utf8(value) => Ok(value.as_bytes().to_vec()),
+        SeedInput::Hex(value) => decode_hex(value),
+        SeedInput::Base58(value) => bs58_decode(value)
+            .into_vec()
+            .map_err(|_| "invalid base58 encoding".to_string()),
+        SeedInput::Pubkey(value) => value
+            .parse::<Pubkey>()
+            .map(|pk| pk.to_bytes().to_vec())
+            .map_err(|_| "invalid public key".to_string()),
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if !value.len().is_multiple_of(2) {
+        return Err("hex string must have an even length".to_string());
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            value
+                .get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| "invalid hex encoding".to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,109 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::instruction::Instruction;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use axum::http::StatusCode;
+
+use super::response::{error_response, InstructionResponse};
+
+// Runtime limits applied when no explicit compute unit limit is requested
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+// The snake_case aliases keep requests written against the original field names working
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudgetRequest {
+    #[serde(alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    #[serde(alias = "heap_frame_bytes")]
+    pub heap_frame_bytes: Option<u32>,
+    // Instructions the budget applies to, used for the default limit
+    #[serde(alias = "instruction_count")]
+    pub instruction_count: Option<u32>,
+}
+
+pub async fn build_compute_budget(Json(payload): Json<ComputeBudgetRequest>) -> impl IntoResponse {
+    // Validate required fields
+    if payload.compute_unit_limit.is_none()
+        && payload.micro_lamports_per_cu.is_none()
+        && payload.heap_frame_bytes.is_none()
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "At least one of `computeUnitLimit`, `microLamportsPerCu` or `heapFrameBytes` is required".to_string(),
+        );
+    }
+
+    // Heap frames are requested in 1KiB steps between 32KiB and 256KiB
+    let heap_ix = match payload.heap_frame_bytes {
+        None => None,
+        Some(bytes) if (32 * 1024..=256 * 1024).contains(&bytes) && bytes.is_multiple_of(1024) => {
+            Some(ComputeBudgetInstruction::request_heap_frame(bytes))
+        }
+        Some(_) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "`heapFrameBytes` must be a multiple of 1024 between 32768 and 262144".to_string(),
+            );
+        }
+    };
+
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        payload.instruction_count.unwrap_or(1),
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    instructions.extend(heap_ix);
+
+    // Always reported here, zero when no price is set
+    InstructionResponse::new(&instructions)
+        .priority_fee(Some(priority_fee.unwrap_or(0)))
+        .respond()
+}
+
+// Build the compute budget instructions to prepend to `instruction_count`
+// other instructions, and the priority fee they add when a price is set.
+// Without an explicit limit the fee is charged on the runtime default.
+pub fn compute_budget_instructions(
+    compute_unit_limit: Option<u32>,
+    micro_lamports_per_cu: Option<u64>,
+    instruction_count: u32,
+) -> Result<(Vec<Instruction>, Option<u64>), String> {
+    let mut instructions = Vec::new();
+
+    if let Some(limit) = compute_unit_limit {
+        if limit == 0 || limit > MAX_COMPUTE_UNIT_LIMIT {
+            return Err(format!(
+                "`computeUnitLimit` must be between 1 and {}",
+                MAX_COMPUTE_UNIT_LIMIT
+            ));
+        }
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+
+    let priority_fee = match micro_lamports_per_cu {
+        None => None,
+        Some(price) => {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+            let units = compute_unit_limit.unwrap_or_else(|| {
+                DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT
+                    .saturating_mul(instruction_count)
+                    .min(MAX_COMPUTE_UNIT_LIMIT)
+            });
+            // Fees are rounded up to the next whole lamport
+            let fee = (units as u128 * price as u128).div_ceil(MICRO_LAMPORTS_PER_LAMPORT);
+            let fee = u64::try_from(fee)
+                .map_err(|_| "Priority fee overflows u64 lamports".to_string())?;
+            Some(fee)
+        }
+    };
+
+    Ok((instructions, priority_fee))
+}
@@ -0,0 +1,699 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use solana_program::program_error::ProgramError;
+use solana_program::program_option::COption;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction::SystemInstruction;
+use solana_program::system_program;
+use solana_sdk::compute_budget;
+use spl_token::instruction::{AuthorityType, TokenInstruction};
+use spl_token_2022::extension::cpi_guard::CpiGuard;
+use spl_token_2022::extension::default_account_state::DefaultAccountState;
+use spl_token_2022::extension::interest_bearing_mint::InterestBearingConfig;
+use spl_token_2022::extension::memo_transfer::MemoTransfer;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseState, BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::state::{Account, AccountState, Mint};
+use base64::engine::general_purpose::STANDARD as base64_engine;
+use base64::Engine;
+use axum::http::StatusCode;
+
+// Human readable name of the programs whose instructions can be decoded
+pub fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    if *program_id == system_program::id() {
+        Some("system")
+    } else if *program_id == spl_token::id() {
+        Some("spl-token")
+    } else if *program_id == spl_token_2022::id() {
+        Some("spl-token-2022")
+    } else if *program_id == spl_associated_token_account::id() {
+        Some("spl-associated-token-account")
+    } else if *program_id == spl_memo::id() || *program_id == spl_memo::v1::id() {
+        Some("spl-memo")
+    } else if *program_id == compute_budget::id() {
+        Some("compute-budget")
+    } else {
+        None
+    }
+}
+
+// Decode instruction data for a known program into `{ "type", "info" }`,
+// naming each account by its role. Returns `None` for unknown programs or
+// data that does not unpack.
+pub fn parse_instruction(program_id: &Pubkey, accounts: &[String], data: &[u8]) -> Option<Value> {
+    match program_name(program_id)? {
+        "system" => parse_system(accounts, data),
+        // Token-2022 shares the original instruction layout, extension instructions stay undecoded
+        "spl-token" | "spl-token-2022" => parse_token(accounts, data),
+        "spl-associated-token-account" => parse_associated_token(accounts, data),
+        "spl-memo" => parse_memo(accounts, data),
+        "compute-budget" => parse_compute_budget(data),
+        _ => None,
+    }
+}
+
+fn parse_system(accounts: &[String], data: &[u8]) -> Option<Value> {
+    let ix = bincode::deserialize::<SystemInstruction>(data).ok()?;
+    let (kind, names, extra): (&str, &[&str], Value) = match ix {
+        SystemInstruction::CreateAccount { lamports, space, owner } => (
+            "createAccount",
+            &["source", "newAccount"],
+            json!({ "lamports": lamports, "space": space, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::Assign { owner } => (
+            "assign",
+            &["account"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        SystemInstruction::Transfer { lamports } => (
+            "transfer",
+            &["source", "destination"],
+            json!({ "lamports": lamports }),
+        ),
+        SystemInstruction::CreateAccountWithSeed { base, seed, lamports, space, owner } => (
+            "createAccountWithSeed",
+            &["source", "newAccount"],
+            json!({
+                "base": base.to_string(),
+                "seed": seed,
+                "lamports": lamports,
+                "space": space,
+                "owner": owner.to_string()
+            }),
+        ),
+        SystemInstruction::AdvanceNonceAccount => (
+            "advanceNonce",
+            &["nonceAccount", "recentBlockhashesSysvar", "nonceAuthority"],
+            json!({}),
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => (
+            "withdrawFromNonce",
+            &["nonceAccount", "destination", "recentBlockhashesSysvar", "rentSysvar", "nonceAuthority"],
+            json!({ "lamports": lamports }),
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => (
+            "initializeNonce",
+            &["nonceAccount", "recentBlockhashesSysvar", "rentSysvar"],
+            json!({ "nonceAuthority": authority.to_string() }),
+        ),
+        SystemInstruction::AuthorizeNonceAccount(authority) => (
+            "authorizeNonce",
+            &["nonceAccount", "nonceAuthority"],
+            json!({ "newAuthorized": authority.to_string() }),
+        ),
+        SystemInstruction::Allocate { space } => (
+            "allocate",
+            &["account"],
+            json!({ "space": space }),
+        ),
+        SystemInstruction::AllocateWithSeed { base, seed, space, owner } => (
+            "allocateWithSeed",
+            &["account", "base"],
+            json!({ "base": base.to_string(), "seed": seed, "space": space, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => (
+            "assignWithSeed",
+            &["account", "base"],
+            json!({ "base": base.to_string(), "seed": seed, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::TransferWithSeed { lamports, from_seed, from_owner } => (
+            "transferWithSeed",
+            &["source", "sourceBase", "destination"],
+            json!({ "lamports": lamports, "sourceSeed": from_seed, "sourceOwner": from_owner.to_string() }),
+        ),
+        SystemInstruction::UpgradeNonceAccount => (
+            "upgradeNonce",
+            &["nonceAccount"],
+            json!({}),
+        ),
+    };
+    Some(parsed(kind, accounts, names, extra))
+}
+
+fn parse_token(accounts: &[String], data: &[u8]) -> Option<Value> {
+    let ix = TokenInstruction::unpack(data).ok()?;
+    let (kind, names, extra): (&str, &[&str], Value) = match ix {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => (
+            "initializeMint",
+            &["mint", "rentSysvar"],
+            json!({
+                "decimals": decimals,
+                "mintAuthority": mint_authority.to_string(),
+                "freezeAuthority": optional_pubkey(freeze_authority)
+            }),
+        ),
+        TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => (
+            "initializeMint2",
+            &["mint"],
+            json!({
+                "decimals": decimals,
+                "mintAuthority": mint_authority.to_string(),
+                "freezeAuthority": optional_pubkey(freeze_authority)
+            }),
+        ),
+        TokenInstruction::InitializeAccount => (
+            "initializeAccount",
+            &["account", "mint", "owner", "rentSysvar"],
+            json!({}),
+        ),
+        TokenInstruction::InitializeAccount2 { owner } => (
+            "initializeAccount2",
+            &["account", "mint", "rentSysvar"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        TokenInstruction::InitializeAccount3 { owner } => (
+            "initializeAccount3",
+            &["account", "mint"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        TokenInstruction::InitializeMultisig { m } => (
+            "initializeMultisig",
+            &["multisig", "rentSysvar"],
+            json!({ "m": m }),
+        ),
+        TokenInstruction::InitializeMultisig2 { m } => (
+            "initializeMultisig2",
+            &["multisig"],
+            json!({ "m": m }),
+        ),
+        TokenInstruction::Transfer { amount } => (
+            "transfer",
+            &["source", "destination", "authority"],
+            json!({ "amount": amount.to_string() }),
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => (
+            "transferChecked",
+            &["source", "mint", "destination", "authority"],
+            json!({ "amount": amount.to_string(), "decimals": decimals }),
+        ),
+        TokenInstruction::Approve { amount } => (
+            "approve",
+            &["source", "delegate", "owner"],
+            json!({ "amount": amount.to_string() }),
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => (
+            "approveChecked",
+            &["source", "mint", "delegate", "owner"],
+            json!({ "amount": amount.to_string(), "decimals": decimals }),
+        ),
+        TokenInstruction::Revoke => (
+            "revoke",
+            &["source", "owner"],
+            json!({}),
+        ),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => (
+            "setAuthority",
+            &["account", "authority"],
+            json!({
+                "authorityType": authority_type_name(&authority_type),
+                "newAuthority": optional_pubkey(new_authority)
+            }),
+        ),
+        TokenInstruction::MintTo { amount } => (
+            "mintTo",
+            &["mint", "account", "mintAuthority"],
+            json!({ "amount": amount.to_string() }),
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => (
+            "mintToChecked",
+            &["mint", "account", "mintAuthority"],
+            json!({ "amount": amount.to_string(), "decimals": decimals }),
+        ),
+        TokenInstruction::Burn { amount } => (
+            "burn",
+            &["account", "mint", "authority"],
+            json!({ "amount": amount.to_string() }),
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => (
+            "burnChecked",
+            &["account", "mint", "authority"],
+            json!({ "amount": amount.to_string(), "decimals": decimals }),
+        ),
+        TokenInstruction::CloseAccount => (
+            "closeAccount",
+            &["account", "destination", "owner"],
+            json!({}),
+        ),
+        TokenInstruction::FreezeAccount => (
+            "freezeAccount",
+            &["account", "mint", "freezeAuthority"],
+            json!({}),
+        ),
+        TokenInstruction::ThawAccount => (
+            "thawAccount",
+            &["account", "mint", "freezeAuthority"],
+            json!({}),
+        ),
+        TokenInstruction::SyncNative => (
+            "syncNative",
+            &["account"],
+            json!({}),
+        ),
+        TokenInstruction::GetAccountDataSize => (
+            "getAccountDataSize",
+            &["mint"],
+            json!({}),
+        ),
+        TokenInstruction::InitializeImmutableOwner => (
+            "initializeImmutableOwner",
+            &["account"],
+            json!({}),
+        ),
+        TokenInstruction::AmountToUiAmount { amount } => (
+            "amountToUiAmount",
+            &["mint"],
+            json!({ "amount": amount.to_string() }),
+        ),
+        TokenInstruction::UiAmountToAmount { ui_amount } => (
+            "uiAmountToAmount",
+            &["mint"],
+            json!({ "uiAmount": ui_amount }),
+        ),
+    };
+    Some(parsed(kind, accounts, names, extra))
+}
+
+fn parse_associated_token(accounts: &[String], data: &[u8]) -> Option<Value> {
+    let (kind, names): (&str, &[&str]) = match data {
+        [] | [0] => (
+            "create",
+            &["source", "account", "wallet", "mint", "systemProgram", "tokenProgram"],
+        ),
+        [1] => (
+            "createIdempotent",
+            &["source", "account", "wallet", "mint", "systemProgram", "tokenProgram"],
+        ),
+        [2] => (
+            "recoverNested",
+            &["nestedSource", "nestedMint", "destination", "nestedOwner", "ownerMint", "wallet", "tokenProgram"],
+        ),
+        _ => return None,
+    };
+    Some(parsed(kind, accounts, names, json!({})))
+}
+
+fn parse_memo(accounts: &[String], data: &[u8]) -> Option<Value> {
+    let memo = std::str::from_utf8(data).ok()?;
+    Some(json!({
+        "type": "memo",
+        "info": { "memo": memo, "signers": accounts }
+    }))
+}
+
+// Compute budget instructions are a one-byte tag followed by little-endian arguments
+fn parse_compute_budget(data: &[u8]) -> Option<Value> {
+    let (tag, rest) = data.split_first()?;
+    let read_u32 = |bytes: &[u8]| bytes.try_into().ok().map(u32::from_le_bytes);
+    let (kind, info) = match tag {
+        1 => ("requestHeapFrame", json!({ "bytes": read_u32(rest)? })),
+        2 => ("setComputeUnitLimit", json!({ "units": read_u32(rest)? })),
+        3 => (
+            "setComputeUnitPrice",
+            json!({ "microLamports": u64::from_le_bytes(rest.try_into().ok()?).to_string() }),
+        ),
+        4 => ("setLoadedAccountsDataSizeLimit", json!({ "bytes": read_u32(rest)? })),
+        _ => return None,
+    };
+    Some(json!({ "type": kind, "info": info }))
+}
+
+// Combine named accounts with the decoded arguments. Accounts past the named
+// roles are multisig signers.
+fn parsed(kind: &str, accounts: &[String], names: &[&str], extra: Value) -> Value {
+    let mut info = Map::new();
+    for (name, account) in names.iter().zip(accounts) {
+        info.insert(name.to_string(), json!(account));
+    }
+    if accounts.len() > names.len() {
+        info.insert("signers".to_string(), json!(accounts[names.len()..]));
+    }
+    if let Value::Object(extra) = extra {
+        info.extend(extra);
+    }
+    json!({ "type": kind, "info": info })
+}
+
+fn optional_pubkey(key: COption<Pubkey>) -> Option<String> {
+    match key {
+        COption::Some(key) => Some(key.to_string()),
+        COption::None => None,
+    }
+}
+
+fn authority_type_name(authority_type: &AuthorityType) -> &'static str {
+    match authority_type {
+        AuthorityType::MintTokens => "mintTokens",
+        AuthorityType::FreezeAccount => "freezeAccount",
+        AuthorityType::AccountOwner => "accountOwner",
+        AuthorityType::CloseAccount => "closeAccount",
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum AccountDataInput {
+    // Base64 encoded bytes
+    Base64(String),
+    // The `[data, encoding]` pair returned by `getAccountInfo`, base64 or base58
+    Encoded(String, String),
+}
+
+#[derive(Deserialize)]
+pub struct DecodeAccountRequest {
+    pub data: AccountDataInput,
+}
+
+pub async fn decode_mint(Json(payload): Json<DecodeAccountRequest>) -> impl IntoResponse {
+    let data = match account_data(&payload.data) {
+        Ok(data) => data,
+        Err(e) => return decode_error(e),
+    };
+    let state = match StateWithExtensions::<Mint>::unpack(&data) {
+        Ok(state) => state,
+        Err(e) => return decode_error(format!("Account data is not a token mint: {}", e)),
+    };
+    let extensions = match extensions_json(&state) {
+        Ok(extensions) => extensions,
+        Err(e) => return decode_error(e),
+    };
+
+    let mint = state.base;
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": {
+            "mint_authority": optional_pubkey(mint.mint_authority),
+            "supply": mint.supply.to_string(),
+            "decimals": mint.decimals,
+            "is_initialized": mint.is_initialized,
+            "freeze_authority": optional_pubkey(mint.freeze_authority),
+            "extensions": extensions,
+        }
+    })))
+}
+
+pub async fn decode_token_account(Json(payload): Json<DecodeAccountRequest>) -> impl IntoResponse {
+    let data = match account_data(&payload.data) {
+        Ok(data) => data,
+        Err(e) => return decode_error(e),
+    };
+    let state = match StateWithExtensions::<Account>::unpack(&data) {
+        Ok(state) => state,
+        Err(e) => return decode_error(format!("Account data is not a token account: {}", e)),
+    };
+    let extensions = match extensions_json(&state) {
+        Ok(extensions) => extensions,
+        Err(e) => return decode_error(e),
+    };
+
+    let account = state.base;
+    // Native accounts hold their rent exempt reserve in `is_native`
+    let rent_exempt_reserve = match account.is_native {
+        COption::Some(reserve) => Some(reserve.to_string()),
+        COption::None => None,
+    };
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": {
+            "mint": account.mint.to_string(),
+            "owner": account.owner.to_string(),
+            "amount": account.amount.to_string(),
+            "delegate": optional_pubkey(account.delegate),
+            "delegated_amount": account.delegated_amount.to_string(),
+            "state": account_state_name(account.state),
+            "is_native": account.is_native.is_some(),
+            "rent_exempt_reserve": rent_exempt_reserve,
+            "close_authority": optional_pubkey(account.close_authority),
+            "extensions": extensions,
+        }
+    })))
+}
+
+fn account_data(input: &AccountDataInput) -> Result<Vec<u8>, String> {
+    let (data, encoding) = match input {
+        AccountDataInput::Base64(data) => (data, "base64"),
+        AccountDataInput::Encoded(data, encoding) => (data, encoding.as_str()),
+    };
+    match encoding {
+        "base64" => base64_engine
+            .decode(data)
+            .map_err(|_| "Invalid base64 account data".to_string()),
+        "base58" => bs58::decode(data)
+            .into_vec()
+            .map_err(|_| "Invalid base58 account data".to_string()),
+        other => Err(format!("Unsupported encoding `{}`, expected base64 or base58", other)),
+    }
+}
+
+fn decode_error(error: String) -> (StatusCode, Json<Value>) {
+    (StatusCode::BAD_REQUEST, Json(json!({
+        "success": false,
+        "error": error
+    })))
+}
+
+// Decode the Token-2022 extensions stored after the base state. Extensions
+// without known fields are listed by type only.
+fn extensions_json<S: BaseState>(state: &StateWithExtensions<S>) -> Result<Vec<Value>, String> {
+    let types = state
+        .get_extension_types()
+        .map_err(|e| format!("Invalid extension data: {}", e))?;
+    let invalid = |e: ProgramError| format!("Invalid extension data: {}", e);
+
+    let mut extensions = Vec::with_capacity(types.len());
+    for extension_type in types {
+        let (kind, info) = match extension_type {
+            ExtensionType::TransferFeeConfig => {
+                let config = state.get_extension::<TransferFeeConfig>().map_err(invalid)?;
+                let fee = |fee: &TransferFee| json!({
+                    "epoch": u64::from(fee.epoch),
+                    "maximumFee": u64::from(fee.maximum_fee).to_string(),
+                    "feeBasisPoints": u16::from(fee.transfer_fee_basis_points),
+                });
+                ("transferFeeConfig", json!({
+                    "transferFeeConfigAuthority": optional_extension_key(config.transfer_fee_config_authority),
+                    "withdrawWithheldAuthority": optional_extension_key(config.withdraw_withheld_authority),
+                    "withheldAmount": u64::from(config.withheld_amount).to_string(),
+                    "olderTransferFee": fee(&config.older_transfer_fee),
+                    "newerTransferFee": fee(&config.newer_transfer_fee),
+                }))
+            }
+            ExtensionType::TransferFeeAmount => {
+                let amount = state.get_extension::<TransferFeeAmount>().map_err(invalid)?;
+                ("transferFeeAmount", json!({
+                    "withheldAmount": u64::from(amount.withheld_amount).to_string(),
+                }))
+            }
+            ExtensionType::MintCloseAuthority => {
+                let close = state.get_extension::<MintCloseAuthority>().map_err(invalid)?;
+                ("mintCloseAuthority", json!({ "closeAuthority": optional_extension_key(close.close_authority) }))
+            }
+            ExtensionType::DefaultAccountState => {
+                let default = state.get_extension::<DefaultAccountState>().map_err(invalid)?;
+                let account_state = AccountState::try_from(default.state)
+                    .map_err(|_| "Invalid extension data: unknown default account state".to_string())?;
+                ("defaultAccountState", json!({ "accountState": account_state_name(account_state) }))
+            }
+            ExtensionType::MemoTransfer => {
+                let memo = state.get_extension::<MemoTransfer>().map_err(invalid)?;
+                ("memoTransfer", json!({
+                    "requireIncomingTransferMemos": bool::from(memo.require_incoming_transfer_memos),
+                }))
+            }
+            ExtensionType::InterestBearingConfig => {
+                let config = state.get_extension::<InterestBearingConfig>().map_err(invalid)?;
+                ("interestBearingConfig", json!({
+                    "rateAuthority": optional_extension_key(config.rate_authority),
+                    "initializationTimestamp": i64::from(config.initialization_timestamp),
+                    "preUpdateAverageRate": i16::from(config.pre_update_average_rate),
+                    "lastUpdateTimestamp": i64::from(config.last_update_timestamp),
+                    "currentRate": i16::from(config.current_rate),
+                }))
+            }
+            ExtensionType::CpiGuard => {
+                let guard = state.get_extension::<CpiGuard>().map_err(invalid)?;
+                ("cpiGuard", json!({ "lockCpi": bool::from(guard.lock_cpi) }))
+            }
+            ExtensionType::PermanentDelegate => {
+                let delegate = state.get_extension::<PermanentDelegate>().map_err(invalid)?;
+                ("permanentDelegate", json!({ "delegate": optional_extension_key(delegate.delegate) }))
+            }
+            ExtensionType::TransferHook => {
+                let hook = state.get_extension::<TransferHook>().map_err(invalid)?;
+                ("transferHook", json!({
+                    "authority": optional_extension_key(hook.authority),
+                    "programId": optional_extension_key(hook.program_id),
+                }))
+            }
+            ExtensionType::MetadataPointer => {
+                let pointer = state.get_extension::<MetadataPointer>().map_err(invalid)?;
+                ("metadataPointer", json!({
+                    "authority": optional_extension_key(pointer.authority),
+                    "metadataAddress": optional_extension_key(pointer.metadata_address),
+                }))
+            }
+            ExtensionType::ImmutableOwner => ("immutableOwner", json!({})),
+            ExtensionType::NonTransferable => ("nonTransferable", json!({})),
+            ExtensionType::NonTransferableAccount => ("nonTransferableAccount", json!({})),
+            other => {
+                // `ConfidentialTransferMint` becomes `confidentialTransferMint`
+                let name = format!("{:?}", other);
+                let mut chars = name.chars();
+                let name = chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default();
+                extensions.push(json!({ "type": name }));
+                continue;
+            }
+        };
+        extensions.push(json!({ "type": kind, "info": info }));
+    }
+    Ok(extensions)
+}
+
+// Extension authorities are stored as a pubkey that is all zeros when unset
+fn optional_extension_key(key: impl Into<Option<Pubkey>>) -> Option<String> {
+    key.into().map(|key| key.to_string())
+}
+
+fn account_state_name(state: AccountState) -> &'static str {
+    match state {
+        AccountState::Uninitialized => "uninitialized",
+        AccountState::Initialized => "initialized",
+        AccountState::Frozen => "frozen",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program_pack::Pack;
+    use spl_token_2022::extension::transfer_fee::TransferFee;
+    use spl_token_2022::extension::StateWithExtensionsMut;
+    use super::super::response::response_json;
+
+    async fn decode_mint_bytes(data: &[u8]) -> (StatusCode, Value) {
+        let request = DecodeAccountRequest { data: AccountDataInput::Base64(base64_engine.encode(data)) };
+        response_json(decode_mint(Json(request)).await).await
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey) -> spl_token::state::Account {
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount: 2_039_285,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::Some(2_039_280),
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_spl_token_mint() {
+        let authority = Pubkey::new_unique();
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(authority),
+            supply: 1_000_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        mint.pack_into_slice(&mut data);
+
+        let (status, body) = decode_mint_bytes(&data).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], json!({
+            "mint_authority": authority.to_string(),
+            "supply": "1000000",
+            "decimals": 6,
+            "is_initialized": true,
+            "freeze_authority": null,
+            "extensions": [],
+        }));
+    }
+
+    #[tokio::test]
+    async fn decodes_native_token_account_from_base58() {
+        let owner = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        token_account(spl_token::native_mint::id(), owner).pack_into_slice(&mut data);
+
+        let encoded = AccountDataInput::Encoded(bs58::encode(&data).into_string(), "base58".to_string());
+        let (status, body) = response_json(decode_token_account(Json(DecodeAccountRequest { data: encoded })).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], json!({
+            "mint": spl_token::native_mint::id().to_string(),
+            "owner": owner.to_string(),
+            "amount": "2039285",
+            "delegate": null,
+            "delegated_amount": "0",
+            "state": "initialized",
+            "is_native": true,
+            "rent_exempt_reserve": "2039280",
+            "close_authority": null,
+            "extensions": [],
+        }));
+    }
+
+    #[tokio::test]
+    async fn decodes_token_2022_mint_extensions() {
+        let (fee_authority, close_authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let types = [ExtensionType::TransferFeeConfig, ExtensionType::MintCloseAuthority];
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(&types).unwrap()];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.transfer_fee_config_authority = Some(fee_authority).try_into().unwrap();
+        config.withheld_amount = 7.into();
+        config.newer_transfer_fee = TransferFee {
+            epoch: 12.into(),
+            maximum_fee: 5_000.into(),
+            transfer_fee_basis_points: 25.into(),
+        };
+        let close = state.init_extension::<MintCloseAuthority>(true).unwrap();
+        close.close_authority = Some(close_authority).try_into().unwrap();
+        state.base = Mint { decimals: 9, is_initialized: true, ..Mint::default() };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        let (status, body) = decode_mint_bytes(&data).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["decimals"], 9);
+        assert_eq!(body["data"]["extensions"], json!([
+            {
+                "type": "transferFeeConfig",
+                "info": {
+                    "transferFeeConfigAuthority": fee_authority.to_string(),
+                    "withdrawWithheldAuthority": null,
+                    "withheldAmount": "7",
+                    "olderTransferFee": { "epoch": 0, "maximumFee": "0", "feeBasisPoints": 0 },
+                    "newerTransferFee": { "epoch": 12, "maximumFee": "5000", "feeBasisPoints": 25 },
+                },
+            },
+            {
+                "type": "mintCloseAuthority",
+                "info": { "closeAuthority": close_authority.to_string() },
+            },
+        ]));
+    }
+
+    #[tokio::test]
+    async fn rejects_token_account_data_as_mint() {
+        // A zeroed mint reads as a valid `COption` tag, so only the length gives it away
+        for mint in [spl_token::native_mint::id(), Pubkey::default()] {
+            let mut data = vec![0; spl_token::state::Account::LEN];
+            token_account(mint, Pubkey::new_unique()).pack_into_slice(&mut data);
+
+            let (status, body) = decode_mint_bytes(&data).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(body["error"].as_str().unwrap().starts_with("Account data is not a token mint"));
+        }
+    }
+}
@@ -1,15 +1,77 @@
 use axum::{Json, response::IntoResponse, http::StatusCode};
-use ed25519_dalek::Keypair;
+use axum::body::{Body, Bytes};
+use axum::extract::FromRequest;
+use axum::http::{header, HeaderMap, Request};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
 use rand::rngs::OsRng;
 use bs58::encode as bs58_encode;
-use serde::Serialize;
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use serde::{Deserialize, Serialize};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signer::keypair::keypair_from_seed_and_derivation_path;
+use solana_sdk::signer::Signer;
 use std::error::Error;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+// Account 0 of the Solana BIP44 coin type, as used by Phantom and `solana-keygen --derivation-path`
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+#[derive(Deserialize, Default)]
+pub struct GenerateKeypairRequest {
+    // 12 or 24; when set the keypair is derived from a fresh BIP39 mnemonic
+    pub words: Option<usize>,
+    pub passphrase: Option<String>,
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DeriveKeypairRequest {
+    pub mnemonic: String,
+    pub passphrase: Option<String>,
+    pub path: Option<String>,
+}
+
+// Default and upper bound for the `/keypair/grind` wall-clock budget
+const DEFAULT_GRIND_SECS: u64 = 10;
+const MAX_GRIND_SECS: u64 = 30;
+
+// A grind already runs a thread per core, so only one may run at a time
+const MAX_CONCURRENT_GRINDS: usize = 1;
+static GRIND_PERMITS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_GRINDS);
+
+#[derive(Deserialize)]
+pub struct GrindKeypairRequest {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    #[serde(rename = "ignoreCase", default)]
+    pub ignore_case: bool,
+    #[serde(rename = "maxAttempts")]
+    pub max_attempts: Option<u64>,
+    #[serde(rename = "timeoutSecs")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct GrindKeypairResponse {
+    pub pubkey: String,
+    pub secret: String,
+    pub attempts: u64,
+    pub elapsed_ms: u128,
+}
 
 #[derive(Serialize)]
 pub struct KeypairResponse {
     pub pubkey: String,
     pub secret: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -37,8 +99,69 @@ impl fmt::Display for KeypairGenerationError {
 
 impl Error for KeypairGenerationError {}
 
-pub async fn generate_keypair() -> impl IntoResponse {
-    match generate_keypair_internal().await {
+pub async fn generate_keypair(headers: HeaderMap, body: Bytes) -> impl IntoResponse {
+    // No body means a plain random keypair, anything else must be a valid JSON request
+    let request = if body.iter().all(u8::is_ascii_whitespace) {
+        GenerateKeypairRequest::default()
+    } else {
+        let mut request = Request::builder();
+        if let Some(content_type) = headers.get(header::CONTENT_TYPE) {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        let request = request.body(Body::from(body)).unwrap();
+        match Json::<GenerateKeypairRequest>::from_request(request, &()).await {
+            Ok(Json(request)) => request,
+            Err(rejection) => {
+                let error_response = ErrorResponse {
+                    success: false,
+                    error: rejection.body_text(),
+                };
+                return (rejection.status(), Json(error_response)).into_response();
+            }
+        }
+    };
+
+    let result = match request.words {
+        None if request.passphrase.is_some() || request.path.is_some() => {
+            let error_response = ErrorResponse {
+                success: false,
+                error: "`passphrase` and `path` require `words`".to_string(),
+            };
+            return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+        }
+        None => generate_keypair_internal().await,
+        Some(words) => {
+            let mnemonic_type = match words {
+                12 => MnemonicType::Words12,
+                24 => MnemonicType::Words24,
+                _ => {
+                    let error_response = ErrorResponse {
+                        success: false,
+                        error: "Mnemonic must be 12 or 24 words".to_string(),
+                    };
+                    return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+                }
+            };
+            let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+            // The generated phrase is always valid, so a failure here is a bad `path`
+            match derive_keypair_internal(
+                mnemonic.phrase(),
+                request.passphrase.as_deref().unwrap_or(""),
+                request.path.as_deref().unwrap_or(DEFAULT_DERIVATION_PATH),
+            ) {
+                Ok(keypair_response) => Ok(keypair_response),
+                Err(e) => {
+                    let error_response = ErrorResponse {
+                        success: false,
+                        error: e.to_string(),
+                    };
+                    return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+                }
+            }
+        }
+    };
+
+    match result {
         Ok(keypair_response) => {
             let success_response = SuccessResponse {
                 success: true,
@@ -62,16 +185,251 @@ async fn generate_keypair_internal() -> Result<KeypairResponse, KeypairGeneratio
     // Generate the keypair
     let kp = Keypair::generate(&mut csprng);
     
+    Ok(encode_keypair(&kp))
+}
+
+fn encode_keypair(kp: &Keypair) -> KeypairResponse {
     // Encode public key
     let pubkey = bs58_encode(kp.public.to_bytes()).into_string();
     
     // Encode the full keypair (64 bytes: 32 private + 32 public)
     let secret = bs58_encode(kp.to_bytes()).into_string();
     
-    Ok(KeypairResponse { pubkey, secret })
+    KeypairResponse {
+        pubkey,
+        secret,
+        mnemonic: None,
+        derivation_path: None,
+    }
+}
+
+pub async fn grind_keypair(Json(payload): Json<GrindKeypairRequest>) -> impl IntoResponse {
+    let prefix = payload.prefix.unwrap_or_default();
+    let suffix = payload.suffix.unwrap_or_default();
+    if prefix.is_empty() && suffix.is_empty() {
+        let error_response = ErrorResponse {
+            success: false,
+            error: "At least one of `prefix` or `suffix` is required".to_string(),
+        };
+        return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+    }
+
+    // Reject patterns that can never appear in a base58 address
+    for (field, pattern) in [("prefix", &prefix), ("suffix", &suffix)] {
+        if let Some(c) = pattern.chars().find(|c| !is_base58_char(*c, payload.ignore_case)) {
+            let error_response = ErrorResponse {
+                success: false,
+                error: format!("Invalid character `{}` in `{}`: not in the base58 alphabet", c, field),
+            };
+            return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+        }
+    }
+
+    let timeout_secs = payload.timeout_secs.unwrap_or(DEFAULT_GRIND_SECS);
+    if timeout_secs == 0 || timeout_secs > MAX_GRIND_SECS {
+        let error_response = ErrorResponse {
+            success: false,
+            error: format!("`timeoutSecs` must be between 1 and {}", MAX_GRIND_SECS),
+        };
+        return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+    }
+
+    if payload.max_attempts == Some(0) {
+        let error_response = ErrorResponse {
+            success: false,
+            error: "`maxAttempts` must be at least 1".to_string(),
+        };
+        return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+    }
+
+    // Busy rather than queued, so waiting requests do not pile up behind the CPU
+    let permit = match GRIND_PERMITS.try_acquire() {
+        Ok(permit) => permit,
+        Err(_) => {
+            let error_response = ErrorResponse {
+                success: false,
+                error: "Another keypair grind is in progress, retry later".to_string(),
+            };
+            return (StatusCode::TOO_MANY_REQUESTS, Json(error_response)).into_response();
+        }
+    };
+
+    let ignore_case = payload.ignore_case;
+    let max_attempts = payload.max_attempts.unwrap_or(u64::MAX);
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(timeout_secs);
+
+    // Key generation is CPU bound, keep it off the async runtime. The permit moves
+    // along so it is held until the threads stop, even if the client disconnects.
+    let result = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        grind_keypair_internal(&prefix, &suffix, ignore_case, max_attempts, deadline)
+    })
+    .await;
+
+    match result {
+        Ok((Some(keypair_response), attempts)) => {
+            let success_response = SuccessResponse {
+                success: true,
+                data: GrindKeypairResponse {
+                    pubkey: keypair_response.pubkey,
+                    secret: keypair_response.secret,
+                    attempts,
+                    elapsed_ms: started.elapsed().as_millis(),
+                },
+            };
+            (StatusCode::OK, Json(success_response)).into_response()
+        }
+        Ok((None, attempts)) => {
+            let error_response = ErrorResponse {
+                success: false,
+                error: format!("No matching keypair found after {} attempts", attempts),
+            };
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(error_response)).into_response()
+        }
+        Err(e) => {
+            let error_response = ErrorResponse {
+                success: false,
+                error: format!("Keypair grinding failed: {}", e),
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)).into_response()
+        }
+    }
+}
+
+fn is_base58_char(c: char, ignore_case: bool) -> bool {
+    const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if ignore_case {
+        ALPHABET.contains(c.to_ascii_lowercase()) || ALPHABET.contains(c.to_ascii_uppercase())
+    } else {
+        ALPHABET.contains(c)
+    }
+}
+
+// Generate keypairs on every core until one matches, the deadline passes or
+// `max_attempts` keypairs have been tried. Returns the match and the attempt count.
+fn grind_keypair_internal(
+    prefix: &str,
+    suffix: &str,
+    ignore_case: bool,
+    max_attempts: u64,
+    deadline: Instant,
+) -> (Option<KeypairResponse>, u64) {
+    let (prefix, suffix) = if ignore_case {
+        (prefix.to_lowercase(), suffix.to_lowercase())
+    } else {
+        (prefix.to_string(), suffix.to_string())
+    };
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let found = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let result = Mutex::new(None);
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut csprng = OsRng;
+                while !found.load(Ordering::Relaxed) && Instant::now() < deadline {
+                    if attempts.fetch_add(1, Ordering::Relaxed) >= max_attempts {
+                        break;
+                    }
+
+                    let kp = Keypair::generate(&mut csprng);
+                    let pubkey = bs58_encode(kp.public.to_bytes()).into_string();
+                    let candidate = if ignore_case { pubkey.to_lowercase() } else { pubkey };
+                    if candidate.starts_with(&prefix)
+                        && candidate.ends_with(&suffix)
+                        && !found.swap(true, Ordering::Relaxed)
+                    {
+                        *result.lock().unwrap() = Some(encode_keypair(&kp));
+                    }
+                }
+            });
+        }
+    });
+
+    // Threads that stopped on the budget still bumped the counter once
+    let attempts = attempts.load(Ordering::Relaxed).min(max_attempts);
+    (result.into_inner().unwrap(), attempts)
+}
+
+pub async fn derive_keypair(Json(payload): Json<DeriveKeypairRequest>) -> impl IntoResponse {
+    if payload.mnemonic.trim().is_empty() {
+        let error_response = ErrorResponse {
+            success: false,
+            error: "Missing required fields".to_string(),
+        };
+        return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+    }
+
+    match derive_keypair_internal(
+        &payload.mnemonic,
+        payload.passphrase.as_deref().unwrap_or(""),
+        payload.path.as_deref().unwrap_or(DEFAULT_DERIVATION_PATH),
+    ) {
+        Ok(keypair_response) => {
+            let success_response = SuccessResponse {
+                success: true,
+                data: keypair_response,
+            };
+            (StatusCode::OK, Json(success_response)).into_response()
+        }
+        Err(e) => {
+            let error_response = ErrorResponse {
+                success: false,
+                error: e.to_string(),
+            };
+            (StatusCode::BAD_REQUEST, Json(error_response)).into_response()
+        }
+    }
+}
+
+// Derive a keypair from a BIP39 mnemonic along a SLIP-0010 ed25519 path
+fn derive_keypair_internal(
+    phrase: &str,
+    passphrase: &str,
+    path: &str,
+) -> Result<KeypairResponse, KeypairGenerationError> {
+    // Normalise whitespace so pasted phrases still pass the checksum
+    let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mnemonic = Mnemonic::from_phrase(&phrase, Language::English).map_err(|e| {
+        KeypairGenerationError {
+            message: format!("Invalid mnemonic: {}", e),
+        }
+    })?;
+
+    // SLIP-0010 only defines hardened derivation for ed25519
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") || segments.any(|segment| !segment.ends_with('\'')) {
+        return Err(KeypairGenerationError {
+            message: format!(
+                "Invalid derivation path `{}`: expected `m/...` with hardened indices only",
+                path
+            ),
+        });
+    }
+    let derivation_path = DerivationPath::from_absolute_path_str(path).map_err(|e| {
+        KeypairGenerationError {
+            message: format!("Invalid derivation path `{}`: {}", path, e),
+        }
+    })?;
+
+    let seed = Seed::new(&mnemonic, passphrase);
+    let kp = keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(derivation_path))
+        .map_err(|e| KeypairGenerationError {
+            message: format!("Failed to derive keypair: {}", e),
+        })?;
+
+    Ok(KeypairResponse {
+        pubkey: kp.pubkey().to_string(),
+        secret: kp.to_base58_string(),
+        mnemonic: Some(mnemonic.into_phrase()),
+        derivation_path: Some(path.to_string()),
+    })
 }
 
 // Alternative endpoint that returns only the public key for security
+#[allow(dead_code)]
 pub async fn generate_pubkey_only() -> impl IntoResponse {
     match generate_pubkey_internal().await {
         Ok(pubkey) => {
@@ -91,6 +449,7 @@ pub async fn generate_pubkey_only() -> impl IntoResponse {
     }
 }
 
+#[allow(dead_code)]
 async fn generate_pubkey_internal() -> Result<String, KeypairGenerationError> {
     let mut csprng = OsRng;
     let kp = Keypair::generate(&mut csprng);
@@ -99,6 +458,7 @@ async fn generate_pubkey_internal() -> Result<String, KeypairGenerationError> {
 }
 
 // Utility function to validate a base58 encoded keypair
+#[allow(dead_code)]
 pub fn validate_keypair_format(keypair_str: &str) -> Result<(), KeypairGenerationError> {
     match bs58::decode(keypair_str).into_vec() {
         Ok(bytes) => {
@@ -114,3 +474,147 @@ pub fn validate_keypair_format(keypair_str: &str) -> Result<(), KeypairGeneratio
         }),
     }
 }
+
+// Parse a secret key in any of the accepted forms into a keypair:
+// - base58 of a 32-byte ed25519 seed (public key is derived)
+// - base58 of a 64-byte keypair as returned by `/keypair` (32 private + 32 public)
+// - a Solana CLI keypair file body, i.e. a JSON array of 32 or 64 bytes
+pub fn keypair_from_secret(secret: &str) -> Result<Keypair, KeypairGenerationError> {
+    let trimmed = secret.trim();
+    let bytes = if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(trimmed).map_err(|_| KeypairGenerationError {
+            message: "Invalid JSON byte array for secret key".to_string(),
+        })?
+    } else {
+        bs58::decode(trimmed).into_vec().map_err(|_| KeypairGenerationError {
+            message: "Invalid base58 encoding for secret key".to_string(),
+        })?
+    };
+
+    match bytes.len() {
+        32 => {
+            let secret = SecretKey::from_bytes(&bytes).map_err(|e| KeypairGenerationError {
+                message: format!("Invalid secret key: {}", e),
+            })?;
+            let public = PublicKey::from(&secret);
+            Ok(Keypair { secret, public })
+        }
+        64 => {
+            let secret = SecretKey::from_bytes(&bytes[..32]).map_err(|e| KeypairGenerationError {
+                message: format!("Invalid secret key: {}", e),
+            })?;
+            let public = PublicKey::from(&secret);
+            // The embedded public half must belong to the private half
+            if public.as_bytes()[..] != bytes[32..] {
+                return Err(KeypairGenerationError {
+                    message: "Public key does not match secret key".to_string(),
+                });
+            }
+            Ok(Keypair { secret, public })
+        }
+        _ => Err(KeypairGenerationError {
+            message: "Secret key must be 32 or 64 bytes".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn derives_wallet_address_for_standard_mnemonic() {
+        // Matches `solana-keygen pubkey prompt://?key=0/0` and Phantom's first account
+        let kp = derive_keypair_internal(MNEMONIC, "", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(kp.pubkey, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(kp.derivation_path.as_deref(), Some(DEFAULT_DERIVATION_PATH));
+
+        let secret = keypair_from_secret(&kp.secret).unwrap();
+        assert_eq!(bs58_encode(secret.public.to_bytes()).into_string(), kp.pubkey);
+    }
+
+    #[test]
+    fn derives_other_accounts_along_the_path() {
+        let kp = derive_keypair_internal(MNEMONIC, "", "m/44'/501'/1'/0'").unwrap();
+        assert_eq!(kp.pubkey, "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb");
+    }
+
+    #[test]
+    fn passphrase_changes_the_seed() {
+        let with = derive_keypair_internal(MNEMONIC, "TREZOR", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(with.pubkey, "7zSmbu6gKkb6HB7UDPtHYjwCWuBHU1D4TpNZFm4sndQe");
+
+        let without = derive_keypair_internal(MNEMONIC, "", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_ne!(with.pubkey, without.pubkey);
+    }
+
+    #[test]
+    fn normalises_whitespace_in_phrase() {
+        let padded = format!("  {}\n", MNEMONIC.replace(' ', "   "));
+        let kp = derive_keypair_internal(&padded, "", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(kp.pubkey, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(kp.mnemonic.as_deref(), Some(MNEMONIC));
+    }
+
+    #[test]
+    fn rejects_non_hardened_path_segments() {
+        for path in ["m/44'/501'/0'/0", "m/44/501'/0'/0'", "44'/501'/0'/0'"] {
+            let err = derive_keypair_internal(MNEMONIC, "", path).err().unwrap();
+            assert!(err.to_string().contains("hardened indices only"), "{}: {}", path, err);
+        }
+    }
+
+    async fn generate(content_type: Option<&str>, body: &'static str) -> StatusCode {
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = content_type {
+            headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+        }
+        generate_keypair(headers, Bytes::from(body)).await.into_response().status()
+    }
+
+    #[tokio::test]
+    async fn generates_with_no_body_or_a_valid_body() {
+        assert_eq!(generate(None, "").await, StatusCode::OK);
+        assert_eq!(generate(Some("application/json"), "{}").await, StatusCode::OK);
+        assert_eq!(generate(Some("application/json"), r#"{"words":12,"passphrase":"x"}"#).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_generate_requests() {
+        assert_eq!(generate(Some("application/json"), r#"{"words":"twelve"}"#).await, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(generate(Some("application/json"), "{").await, StatusCode::BAD_REQUEST);
+        assert_eq!(generate(Some("text/plain"), r#"{"words":12}"#).await, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(generate(None, r#"{"words":12}"#).await, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(generate(Some("application/json"), r#"{"words":15}"#).await, StatusCode::BAD_REQUEST);
+        assert_eq!(generate(Some("application/json"), r#"{"passphrase":"x"}"#).await, StatusCode::BAD_REQUEST);
+        assert_eq!(generate(Some("application/json"), r#"{"path":"m/44'/501'/0'/0'"}"#).await, StatusCode::BAD_REQUEST);
+        assert_eq!(generate(Some("application/json"), r#"{"words":12,"path":"m/44/501"}"#).await, StatusCode::BAD_REQUEST);
+        assert_eq!(generate(Some("application/json"), r#"{"words":24,"path":"44'/501'"}"#).await, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_grind_limits() {
+        for body in [
+            r#"{"prefix":"a","maxAttempts":0}"#,
+            r#"{"prefix":"a","timeoutSecs":0}"#,
+            r#"{"prefix":"a","timeoutSecs":31}"#,
+        ] {
+            let request: GrindKeypairRequest = serde_json::from_str(body).unwrap();
+            let status = grind_keypair(Json(request)).await.into_response().status();
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_word_counts_and_checksums() {
+        let eleven_words = "abandon ".repeat(10) + "about";
+        let err = derive_keypair_internal(&eleven_words, "", DEFAULT_DERIVATION_PATH).err().unwrap();
+        assert!(err.to_string().starts_with("Invalid mnemonic"), "{}", err);
+
+        let bad_checksum = "abandon ".repeat(12);
+        let err = derive_keypair_internal(&bad_checksum, "", DEFAULT_DERIVATION_PATH).err().unwrap();
+        assert!(err.to_string().starts_with("Invalid mnemonic"), "{}", err);
+    }
+}
@@ -0,0 +1,186 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::address_lookup_table::instruction as lookup_table_instruction;
+use solana_program::address_lookup_table::state::LOOKUP_TABLE_MAX_ADDRESSES;
+use solana_program::pubkey::Pubkey;
+use axum::http::StatusCode;
+
+use super::response::{error_response, instruction_response, InstructionResponse};
+use super::token::parse_address;
+
+// Addresses per `extend`, small enough that each fits a transaction with a
+// separate payer and compute budget instructions
+pub const EXTEND_CHUNK_SIZE: usize = 20;
+
+#[derive(Deserialize)]
+pub struct CreateLookupTableRequest {
+    pub authority: String,
+    // Defaults to `authority`
+    pub payer: Option<String>,
+    // A recent slot, part of the table address derivation
+    #[serde(rename = "recentSlot")]
+    pub recent_slot: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ExtendLookupTableRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+    // Funds the table's growth, defaults to `authority`
+    pub payer: Option<String>,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct LookupTableAuthorityRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+}
+
+#[derive(Deserialize)]
+pub struct CloseLookupTableRequest {
+    #[serde(rename = "lookupTable")]
+    pub lookup_table: String,
+    pub authority: String,
+    // Receives the table's lamports, defaults to `authority`
+    pub recipient: Option<String>,
+}
+
+pub async fn create_lookup_table(Json(payload): Json<CreateLookupTableRequest>) -> impl IntoResponse {
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let payer = match payload.payer.as_deref() {
+        None => authority,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    let (ix, lookup_table) = lookup_table_instruction::create_lookup_table(authority, payer, payload.recent_slot);
+    let (_, bump) = lookup_table_instruction::derive_lookup_table_address(&authority, payload.recent_slot);
+
+    InstructionResponse::new(&[ix])
+        .field("lookup_table", lookup_table.to_string())
+        .field("bump", bump)
+        .respond()
+}
+
+pub async fn extend_lookup_table(Json(payload): Json<ExtendLookupTableRequest>) -> impl IntoResponse {
+    let lookup_table = match parse_address(&payload.lookup_table, "lookupTable") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let payer = match payload.payer.as_deref() {
+        None => authority,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    if payload.addresses.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "At least one address is required".to_string());
+    }
+    if payload.addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("A lookup table holds at most {} addresses", LOOKUP_TABLE_MAX_ADDRESSES),
+        );
+    }
+
+    let mut addresses: Vec<Pubkey> = Vec::with_capacity(payload.addresses.len());
+    for (i, address) in payload.addresses.iter().enumerate() {
+        let address = match address.parse::<Pubkey>() {
+            Ok(pk) => pk,
+            Err(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid `addresses` entry at index {}", i),
+                );
+            }
+        };
+        if addresses.contains(&address) {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Duplicate `addresses` entry at index {}", i),
+            );
+        }
+        addresses.push(address);
+    }
+
+    // Each chunk is its own `extend`, to be sent in order in separate transactions
+    let instructions = addresses
+        .chunks(EXTEND_CHUNK_SIZE)
+        .map(|chunk| {
+            lookup_table_instruction::extend_lookup_table(lookup_table, authority, Some(payer), chunk.to_vec())
+        })
+        .collect::<Vec<_>>();
+
+    InstructionResponse::new(&instructions)
+        .field("lookup_table", lookup_table.to_string())
+        .respond()
+}
+
+pub async fn freeze_lookup_table(Json(payload): Json<LookupTableAuthorityRequest>) -> impl IntoResponse {
+    let lookup_table = match parse_address(&payload.lookup_table, "lookupTable") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(lookup_table_instruction::freeze_lookup_table(lookup_table, authority)),
+        "freeze_lookup_table",
+    )
+}
+
+pub async fn deactivate_lookup_table(Json(payload): Json<LookupTableAuthorityRequest>) -> impl IntoResponse {
+    let lookup_table = match parse_address(&payload.lookup_table, "lookupTable") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(lookup_table_instruction::deactivate_lookup_table(lookup_table, authority)),
+        "deactivate_lookup_table",
+    )
+}
+
+pub async fn close_lookup_table(Json(payload): Json<CloseLookupTableRequest>) -> impl IntoResponse {
+    let lookup_table = match parse_address(&payload.lookup_table, "lookupTable") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let recipient = match payload.recipient.as_deref() {
+        None => authority,
+        Some(recipient) => match parse_address(recipient, "recipient") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    // Only a table deactivated long enough ago for its slot to leave the slot hashes can close
+    instruction_response(
+        Ok(lookup_table_instruction::close_lookup_table(lookup_table, authority, recipient)),
+        "close_lookup_table",
+    )
+}
@@ -0,0 +1,87 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::instruction::Instruction;
+use solana_program::pubkey::Pubkey;
+use axum::http::StatusCode;
+
+use super::response::{error_response, InstructionResponse};
+
+// The memo program validates and logs the memo and every signer within the
+// default 200k compute units per instruction. With one signer that leaves room
+// for 566 bytes of ASCII; each further signer costs about as much as 50 bytes.
+// Multi-byte UTF-8 costs more per byte, so these are upper bounds.
+pub const MAX_MEMO_LEN: usize = 566;
+const MEMO_BYTES_PER_EXTRA_SIGNER: usize = 50;
+
+#[derive(Deserialize)]
+pub struct MemoRequest {
+    pub memo: String,
+    // Accounts that must sign the memo
+    #[serde(default)]
+    pub signers: Vec<String>,
+}
+
+pub async fn build_memo(Json(payload): Json<MemoRequest>) -> impl IntoResponse {
+    // Parse signer public keys
+    let mut signers = Vec::with_capacity(payload.signers.len());
+    for (i, signer) in payload.signers.iter().enumerate() {
+        match signer.parse::<Pubkey>() {
+            Ok(pk) => signers.push(pk),
+            Err(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid `signers` address at index {}", i),
+                );
+            }
+        }
+    }
+
+    let ix = match memo_instruction(&payload.memo, &signers) {
+        Ok(instruction) => instruction,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    InstructionResponse::new(&[ix]).respond()
+}
+
+// Build an SPL Memo v2 instruction signed by `signers`
+pub fn memo_instruction(memo: &str, signers: &[Pubkey]) -> Result<Instruction, String> {
+    if memo.is_empty() {
+        return Err("Memo must not be empty".to_string());
+    }
+    let max_len = max_memo_len(signers.len());
+    if max_len == 0 {
+        return Err(format!("A memo cannot have more than {} signers", signers.len() - 1));
+    }
+    if memo.len() > max_len {
+        return Err(format!(
+            "Memo is {} bytes, exceeding the {} byte limit for {} signers",
+            memo.len(),
+            max_len,
+            signers.len()
+        ));
+    }
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+    Ok(spl_memo::build_memo(memo.as_bytes(), &signer_refs))
+}
+
+// Longest memo the program can process with `signers` signers
+fn max_memo_len(signers: usize) -> usize {
+    MAX_MEMO_LEN.saturating_sub(signers.saturating_sub(1) * MEMO_BYTES_PER_EXTRA_SIGNER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_memo_limit_per_extra_signer() {
+        let signers = (0..12).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let memo = "a".repeat(MAX_MEMO_LEN);
+        assert!(memo_instruction(&memo, &signers[..1]).is_ok());
+        assert!(memo_instruction(&memo, &signers[..2]).is_err());
+        assert!(memo_instruction(&memo[..MAX_MEMO_LEN - 50], &signers[..2]).is_ok());
+        assert!(memo_instruction(&memo[..16], &signers[..12]).is_ok());
+        assert!(memo_instruction(&memo[..17], &signers[..12]).is_err());
+    }
+}
@@ -1,11 +1,22 @@
 use axum::{Json, response::IntoResponse};
 use serde::{Deserialize, Serialize};
-use ed25519_dalek::{Keypair, PublicKey, Signature, Signer, Verifier};
+use ed25519_dalek::{PublicKey, Signature, Signer, Verifier};
 use bs58::{encode as bs58_encode, decode as bs58_decode};
 use base64::engine::general_purpose::STANDARD as base64_engine;
 use base64::Engine;
 use axum::http::StatusCode;
 use serde_json::json;
+use solana_sdk::ed25519_instruction::{
+    PUBKEY_SERIALIZED_SIZE, SIGNATURE_OFFSETS_SERIALIZED_SIZE, SIGNATURE_OFFSETS_START, SIGNATURE_SERIALIZED_SIZE,
+};
+use solana_sdk::ed25519_program;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::signature::Signature as TransactionSignature;
+use solana_sdk::transaction::VersionedTransaction;
+
+use super::keypair::keypair_from_secret;
+use super::response::{error_response, InstructionResponse};
 
 #[derive(Deserialize)]
 pub struct SignRequest {
@@ -32,40 +43,15 @@ pub async fn sign_message(Json(payload): Json<SignRequest>) -> impl IntoResponse
         );
     }
 
-    // Decode the secret key from base58
-    let secret_bytes = match bs58_decode(&payload.secret).into_vec() {
-        Ok(bytes) => bytes,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid base58 encoding for secret key"
-                }))
-            );
-        }
-    };
-
-    // Validate secret key length (ed25519 keys should be 32 bytes)
-    if secret_bytes.len() != 32 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Secret key must be exactly 32 bytes"
-            }))
-        );
-    }
-
-    // Create keypair from secret bytes
-    let kp = match Keypair::from_bytes(&secret_bytes) {
+    // Accepts a 32-byte seed, a 64-byte keypair or a JSON byte array
+    let kp = match keypair_from_secret(&payload.secret) {
         Ok(keypair) => keypair,
         Err(e) => {
             return (
                 StatusCode::BAD_REQUEST,
                 Json(json!({
                     "success": false,
-                    "error": format!("Invalid secret key: {}", e)
+                    "error": e.to_string()
                 }))
             );
         }
@@ -201,3 +187,433 @@ pub async fn verify_message(Json(payload): Json<VerifyRequest>) -> impl IntoResp
         }
     })))
 }
+
+#[derive(Deserialize)]
+pub struct SignTransactionRequest {
+    // Base64 of a serialized legacy or v0 transaction
+    pub transaction: String,
+    pub secrets: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SignTransactionResponse {
+    pub transaction: String,
+    pub signed: Vec<String>,
+    pub missing_signers: Vec<String>,
+    pub complete: bool,
+}
+
+pub async fn sign_transaction(Json(payload): Json<SignTransactionRequest>) -> impl IntoResponse {
+    // Validate required fields
+    if payload.transaction.is_empty() || payload.secrets.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": "Missing required fields"
+            }))
+        );
+    }
+
+    // Decode the transaction from base64
+    let tx_bytes = match base64_engine.decode(&payload.transaction) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid base64 encoding for transaction"
+                }))
+            );
+        }
+    };
+
+    let mut tx = match bincode::deserialize::<VersionedTransaction>(&tx_bytes) {
+        Ok(tx) => tx,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Invalid transaction: {}", e)
+                }))
+            );
+        }
+    };
+
+    // Also guarantees a signature slot for every required signer
+    if let Err(e) = tx.sanitize() {
+        return error_response(StatusCode::BAD_REQUEST, format!("Invalid transaction: {}", e));
+    }
+    let num_signers = tx.message.header().num_required_signatures as usize;
+
+    // Every signer signs the same serialized message
+    let message_bytes = tx.message.serialize();
+    let mut signed = Vec::with_capacity(payload.secrets.len());
+    for (i, secret) in payload.secrets.iter().enumerate() {
+        let kp = match keypair_from_secret(secret) {
+            Ok(keypair) => keypair,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Invalid secret {}: {}", i, e)
+                    }))
+                );
+            }
+        };
+
+        // Place the signature in the slot of the matching required signer
+        let pubkey = bs58_encode(kp.public.to_bytes()).into_string();
+        let slot = tx.message.static_account_keys()[..num_signers]
+            .iter()
+            .position(|key| key.to_bytes() == kp.public.to_bytes());
+        let slot = match slot {
+            Some(slot) => slot,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("{} is not a required signer of this transaction", pubkey)
+                    }))
+                );
+            }
+        };
+
+        let sig: Signature = kp.sign(&message_bytes);
+        tx.signatures[slot] = TransactionSignature::from(sig.to_bytes());
+        signed.push(pubkey);
+    }
+
+    let missing_signers = tx.message.static_account_keys()[..num_signers]
+        .iter()
+        .zip(tx.signatures.iter())
+        .filter(|(_, sig)| **sig == TransactionSignature::default())
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+
+    let tx_bytes = match bincode::serialize(&tx) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Failed to serialize transaction: {}", e)
+                }))
+            );
+        }
+    };
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": SignTransactionResponse {
+            transaction: base64_engine.encode(&tx_bytes),
+            signed,
+            complete: missing_signers.is_empty(),
+            missing_signers,
+        }
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct Ed25519SignatureInput {
+    pub message: String,
+    // How `message` is encoded: "utf8" (default), "base58" or "base64"
+    pub encoding: Option<String>,
+    // Either `pubkey` and `signature` from an off-chain signer, or `secret` to sign here
+    pub pubkey: Option<String>,
+    pub signature: Option<String>,
+    pub secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Ed25519InstructionRequest {
+    pub signatures: Vec<Ed25519SignatureInput>,
+}
+
+#[derive(Serialize)]
+pub struct Ed25519SignatureResponse {
+    pub pubkey: String,
+    pub signature: String,
+    pub message: String,
+}
+
+pub async fn ed25519_instruction(Json(payload): Json<Ed25519InstructionRequest>) -> impl IntoResponse {
+    if payload.signatures.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "At least one signature is required".to_string());
+    }
+    if payload.signatures.len() > u8::MAX as usize {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("At most {} signatures fit one Ed25519 instruction", u8::MAX),
+        );
+    }
+
+    let mut entries = Vec::with_capacity(payload.signatures.len());
+    for (i, input) in payload.signatures.iter().enumerate() {
+        match ed25519_signature(input) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, format!("Signature {}: {}", i, e)),
+        }
+    }
+
+    let data_len = ed25519_offsets_end(entries.len())
+        + entries
+            .iter()
+            .map(|(_, _, message)| PUBKEY_SERIALIZED_SIZE + SIGNATURE_SERIALIZED_SIZE + message.len())
+            .sum::<usize>();
+    if data_len > PACKET_DATA_SIZE {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Instruction data is {} bytes, exceeding the {} byte transaction limit",
+                data_len, PACKET_DATA_SIZE
+            ),
+        );
+    }
+
+    let signatures = entries
+        .iter()
+        .zip(payload.signatures.iter())
+        .map(|((pubkey, signature, _), input)| Ed25519SignatureResponse {
+            pubkey: bs58_encode(pubkey.to_bytes()).into_string(),
+            signature: base64_engine.encode(signature.to_bytes()),
+            message: input.message.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    InstructionResponse::new(&[ed25519_verify_instruction(&entries)])
+        .field("signatures", signatures)
+        .respond()
+}
+
+// Where the signature data starts, after the count, padding byte and every offsets entry
+fn ed25519_offsets_end(count: usize) -> usize {
+    SIGNATURE_OFFSETS_START + count * SIGNATURE_OFFSETS_SERIALIZED_SIZE
+}
+
+// Lay out an Ed25519 program instruction verifying every entry. Offsets for all
+// signatures come first, followed by each pubkey, signature and message. An
+// instruction index of u16::MAX points the precompile at this instruction's own
+// data, so it can sit anywhere in the transaction. Callers keep the data within
+// a packet, which also keeps every offset within u16.
+fn ed25519_verify_instruction(entries: &[(PublicKey, Signature, Vec<u8>)]) -> Instruction {
+    let mut data = Vec::new();
+    data.extend_from_slice(&[entries.len() as u8, 0]);
+    let mut offset = ed25519_offsets_end(entries.len());
+    for (_, _, message) in entries.iter() {
+        let public_key_offset = offset;
+        let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+        offset = message_data_offset + message.len();
+        for field in [
+            signature_offset,
+            u16::MAX as usize,
+            public_key_offset,
+            u16::MAX as usize,
+            message_data_offset,
+            message.len(),
+            u16::MAX as usize,
+        ] {
+            data.extend_from_slice(&(field as u16).to_le_bytes());
+        }
+    }
+    for (pubkey, signature, message) in entries.iter() {
+        data.extend_from_slice(pubkey.as_bytes());
+        data.extend_from_slice(&signature.to_bytes());
+        data.extend_from_slice(message);
+    }
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+// Resolve one entry to a pubkey, signature and message the precompile will accept
+fn ed25519_signature(input: &Ed25519SignatureInput) -> Result<(PublicKey, Signature, Vec<u8>), String> {
+    let message = match input.encoding.as_deref().unwrap_or("utf8") {
+        "utf8" => input.message.as_bytes().to_vec(),
+        "base58" => bs58_decode(&input.message)
+            .into_vec()
+            .map_err(|_| "Invalid base58 encoding for message".to_string())?,
+        "base64" => base64_engine
+            .decode(&input.message)
+            .map_err(|_| "Invalid base64 encoding for message".to_string())?,
+        other => {
+            return Err(format!(
+                "Unknown `encoding` `{}`, expected `utf8`, `base58` or `base64`",
+                other
+            ))
+        }
+    };
+    if message.is_empty() {
+        return Err("`message` is required".to_string());
+    }
+
+    if let Some(secret) = input.secret.as_deref() {
+        if input.pubkey.is_some() || input.signature.is_some() {
+            return Err("Provide either `secret` or `pubkey` and `signature`, not both".to_string());
+        }
+        let kp = keypair_from_secret(secret).map_err(|e| e.to_string())?;
+        let signature = kp.sign(&message);
+        return Ok((kp.public, signature, message));
+    }
+
+    let (pubkey, signature) = match (input.pubkey.as_deref(), input.signature.as_deref()) {
+        (Some(pubkey), Some(signature)) => (pubkey, signature),
+        _ => return Err("Provide either `secret` or `pubkey` and `signature`".to_string()),
+    };
+    let pub_bytes = bs58_decode(pubkey)
+        .into_vec()
+        .map_err(|_| "Invalid base58 encoding for public key".to_string())?;
+    if pub_bytes.len() != PUBKEY_SERIALIZED_SIZE {
+        return Err("Public key must be exactly 32 bytes".to_string());
+    }
+    let pubkey = PublicKey::from_bytes(&pub_bytes).map_err(|e| format!("Invalid public key: {}", e))?;
+    let sig_bytes = base64_engine
+        .decode(signature)
+        .map_err(|_| "Invalid base64 encoding for signature".to_string())?;
+    if sig_bytes.len() != SIGNATURE_SERIALIZED_SIZE {
+        return Err("Signature must be exactly 64 bytes".to_string());
+    }
+    let signature = Signature::from_bytes(&sig_bytes).map_err(|e| format!("Invalid signature: {}", e))?;
+
+    // The precompile fails the whole transaction on a bad signature, so catch it here
+    if pubkey.verify(&message, &signature).is_err() {
+        return Err("Signature does not verify against `pubkey` and `message`".to_string());
+    }
+    Ok((pubkey, signature, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Keypair;
+    use serde_json::Value;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::message::{Message, VersionedMessage};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::system_instruction;
+    use super::super::response::response_json;
+    use super::super::transaction::encode_unsigned_transaction;
+    use solana_sdk::ed25519_instruction::{new_ed25519_instruction, verify};
+    use solana_sdk::feature_set::FeatureSet;
+
+    fn keypair(seed: u8) -> Keypair {
+        keypair_from_secret(&bs58_encode([seed; 32]).into_string()).unwrap()
+    }
+
+    fn entry(kp: &Keypair, message: &[u8]) -> (PublicKey, Signature, Vec<u8>) {
+        (kp.public, kp.sign(message), message.to_vec())
+    }
+
+    #[test]
+    fn single_signature_matches_sdk_layout() {
+        let kp = keypair(7);
+        let message = [0u8, 1, 2, 255, 254, 128];
+        let ix = ed25519_verify_instruction(&[entry(&kp, &message)]);
+        assert_eq!(ix, new_ed25519_instruction(&kp, &message));
+    }
+
+    #[test]
+    fn multiple_signatures_pass_the_precompile() {
+        let entries = [entry(&keypair(1), b"first"), entry(&keypair(2), &[9u8; 100]), entry(&keypair(3), b"x")];
+        let ix = ed25519_verify_instruction(&entries);
+        assert_eq!(ix.data[0], 3);
+        assert!(verify(&ix.data, &[&ix.data], &FeatureSet::all_enabled()).is_ok());
+    }
+
+    #[test]
+    fn decodes_binary_messages() {
+        let kp = keypair(4);
+        let message = vec![0u8, 159, 146, 150];
+        let signature = base64_engine.encode(kp.sign(&message).to_bytes());
+        for (encoded, encoding) in [
+            (bs58_encode(&message).into_string(), "base58"),
+            (base64_engine.encode(&message), "base64"),
+        ] {
+            let input = Ed25519SignatureInput {
+                message: encoded,
+                encoding: Some(encoding.to_string()),
+                pubkey: Some(bs58_encode(kp.public.to_bytes()).into_string()),
+                signature: Some(signature.clone()),
+                secret: None,
+            };
+            let (_, _, decoded) = ed25519_signature(&input).unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    fn pubkey(kp: &Keypair) -> Pubkey {
+        Pubkey::new_from_array(kp.public.to_bytes())
+    }
+
+    fn secret(seed: u8) -> String {
+        bs58_encode([seed; 32]).into_string()
+    }
+
+    // A transfer from `sender`, with fees paid by `payer`, so both must sign
+    fn two_signer_message(payer: &Keypair, sender: &Keypair) -> VersionedMessage {
+        let ix = system_instruction::transfer(&pubkey(sender), &Pubkey::new_unique(), 1);
+        VersionedMessage::Legacy(Message::new_with_blockhash(&[ix], Some(&pubkey(payer)), &Hash::new_unique()))
+    }
+
+    async fn sign(message: VersionedMessage, secrets: Vec<String>) -> (StatusCode, Value) {
+        let (bytes, _) = encode_unsigned_transaction(message, &[]).unwrap();
+        let request = SignTransactionRequest { transaction: base64_engine.encode(bytes), secrets };
+        response_json(sign_transaction(Json(request)).await).await
+    }
+
+    fn signed_transaction(body: &Value) -> VersionedTransaction {
+        let bytes = base64_engine.decode(body["data"]["transaction"].as_str().unwrap()).unwrap();
+        bincode::deserialize(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn signs_into_the_matching_slot_and_reports_missing_signers() {
+        let (payer, sender) = (keypair(1), keypair(2));
+        let (status, body) = sign(two_signer_message(&payer, &sender), vec![secret(2)]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["signed"], json!([pubkey(&sender).to_string()]));
+        assert_eq!(body["data"]["missing_signers"], json!([pubkey(&payer).to_string()]));
+        assert_eq!(body["data"]["complete"], json!(false));
+
+        let tx = signed_transaction(&body);
+        let message = tx.message.serialize();
+        assert_eq!(tx.message.static_account_keys()[1], pubkey(&sender));
+        assert_eq!(tx.signatures[0], TransactionSignature::default());
+        assert!(tx.signatures[1].verify(&pubkey(&sender).to_bytes(), &message));
+    }
+
+    #[tokio::test]
+    async fn completes_when_every_signer_signs() {
+        let (payer, sender) = (keypair(1), keypair(2));
+        let (status, body) = sign(two_signer_message(&payer, &sender), vec![secret(2), secret(1)]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["complete"], json!(true));
+        assert!(signed_transaction(&body).verify_with_results().iter().all(|valid| *valid));
+    }
+
+    #[tokio::test]
+    async fn refuses_keys_that_are_not_required_signers() {
+        let (status, body) = sign(two_signer_message(&keypair(1), &keypair(2)), vec![secret(3)]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().contains("not a required signer"));
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_messages() {
+        let mut message = two_signer_message(&keypair(1), &keypair(2));
+        if let VersionedMessage::Legacy(message) = &mut message {
+            message.header.num_readonly_unsigned_accounts = 10;
+        }
+        let (status, _) = sign(message, vec![secret(2)]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
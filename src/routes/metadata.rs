@@ -0,0 +1,267 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::instruction::Instruction;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_program;
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::instructions::{
+    CreateMetadataAccountV3, CreateMetadataAccountV3InstructionArgs, UpdateMetadataAccountV2,
+    UpdateMetadataAccountV2InstructionArgs,
+};
+use mpl_token_metadata::types::{Creator, DataV2};
+use mpl_token_metadata::{MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+use axum::http::StatusCode;
+
+use super::response::{error_response, InstructionResponse};
+use super::token::parse_address;
+
+const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+
+#[derive(Deserialize)]
+pub struct MetadataDataInput {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    // Royalty in basis points
+    #[serde(rename = "sellerFeeBasisPoints", default)]
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<CreatorInput>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatorInput {
+    pub address: String,
+    // Percentage of royalties, shares add up to 100
+    pub share: u8,
+    // Only the signing update authority can be marked verified
+    #[serde(default)]
+    pub verified: bool,
+}
+
+#[derive(Deserialize)]
+pub struct CreateMetadataRequest {
+    pub mint: String,
+    #[serde(rename = "mintAuthority")]
+    pub mint_authority: String,
+    // Defaults to `mintAuthority`
+    pub payer: Option<String>,
+    // Defaults to `mintAuthority`
+    #[serde(rename = "updateAuthority")]
+    pub update_authority: Option<String>,
+    #[serde(flatten)]
+    pub data: MetadataDataInput,
+    #[serde(rename = "isMutable", default = "default_is_mutable")]
+    pub is_mutable: bool,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMetadataRequest {
+    pub mint: String,
+    #[serde(rename = "updateAuthority")]
+    pub update_authority: String,
+    // Replaces the name, symbol, uri, royalty and creators together
+    pub data: Option<MetadataDataInput>,
+    #[serde(rename = "newUpdateAuthority")]
+    pub new_update_authority: Option<String>,
+    // Can only be set to true
+    #[serde(rename = "primarySaleHappened")]
+    pub primary_sale_happened: Option<bool>,
+    // Can only be set to false
+    #[serde(rename = "isMutable")]
+    pub is_mutable: Option<bool>,
+}
+
+fn default_is_mutable() -> bool {
+    true
+}
+
+pub async fn create_metadata(Json(payload): Json<CreateMetadataRequest>) -> impl IntoResponse {
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let mint_authority = match parse_address(&payload.mint_authority, "mintAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let payer = match payload.payer.as_deref() {
+        None => mint_authority,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    let update_authority = match payload.update_authority.as_deref() {
+        None => mint_authority,
+        Some(update_authority) => match parse_address(update_authority, "updateAuthority") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    // The update authority only signs when it is already signing as mint authority or payer
+    let update_authority_signs = update_authority == mint_authority || update_authority == payer;
+    let data = match metadata_data(&payload.data, &update_authority, update_authority_signs) {
+        Ok(data) => data,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    let (metadata, _) = Metadata::find_pda(&mint);
+    let ix = CreateMetadataAccountV3 {
+        metadata,
+        mint,
+        mint_authority,
+        payer,
+        update_authority: (update_authority, update_authority_signs),
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction(CreateMetadataAccountV3InstructionArgs {
+        data,
+        is_mutable: payload.is_mutable,
+        collection_details: None,
+    });
+
+    metadata_response(ix, &metadata)
+}
+
+pub async fn update_metadata(Json(payload): Json<UpdateMetadataRequest>) -> impl IntoResponse {
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let update_authority = match parse_address(&payload.update_authority, "updateAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let new_update_authority = match payload.new_update_authority.as_deref() {
+        None => None,
+        Some(new_update_authority) => match parse_address(new_update_authority, "newUpdateAuthority") {
+            Ok(pk) => Some(pk),
+            Err(response) => return response,
+        },
+    };
+    if payload.primary_sale_happened == Some(false) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "`primarySaleHappened` cannot be reset to false".to_string(),
+        );
+    }
+    if payload.is_mutable == Some(true) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "`isMutable` can only be set to false".to_string(),
+        );
+    }
+    let data = match payload.data.as_ref() {
+        None => None,
+        Some(data) => match metadata_data(data, &update_authority, true) {
+            Ok(data) => Some(data),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        },
+    };
+    if data.is_none()
+        && new_update_authority.is_none()
+        && payload.primary_sale_happened.is_none()
+        && payload.is_mutable.is_none()
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "Nothing to update: provide `data`, `newUpdateAuthority`, `primarySaleHappened` or `isMutable`"
+                .to_string(),
+        );
+    }
+
+    let (metadata, _) = Metadata::find_pda(&mint);
+    let ix = UpdateMetadataAccountV2 {
+        metadata,
+        update_authority,
+    }
+    .instruction(UpdateMetadataAccountV2InstructionArgs {
+        data,
+        new_update_authority,
+        primary_sale_happened: payload.primary_sale_happened,
+        is_mutable: payload.is_mutable,
+    });
+
+    metadata_response(ix, &metadata)
+}
+
+// Validate metadata fields against the Token Metadata program limits
+fn metadata_data(
+    input: &MetadataDataInput,
+    update_authority: &Pubkey,
+    update_authority_signs: bool,
+) -> Result<DataV2, String> {
+    for (field, value, limit) in [
+        ("name", &input.name, MAX_NAME_LENGTH),
+        ("symbol", &input.symbol, MAX_SYMBOL_LENGTH),
+        ("uri", &input.uri, MAX_URI_LENGTH),
+    ] {
+        if value.len() > limit {
+            return Err(format!(
+                "`{}` is {} bytes, exceeding the {} byte limit",
+                field,
+                value.len(),
+                limit
+            ));
+        }
+    }
+    if input.seller_fee_basis_points > MAX_SELLER_FEE_BASIS_POINTS {
+        return Err(format!(
+            "`sellerFeeBasisPoints` must be at most {}",
+            MAX_SELLER_FEE_BASIS_POINTS
+        ));
+    }
+
+    let creators = match input.creators.as_ref() {
+        None => None,
+        Some(creators) => {
+            if creators.is_empty() || creators.len() > MAX_CREATOR_LIMIT {
+                return Err(format!("`creators` must list between 1 and {} creators", MAX_CREATOR_LIMIT));
+            }
+            let mut parsed: Vec<Creator> = Vec::with_capacity(creators.len());
+            for (i, creator) in creators.iter().enumerate() {
+                let address = creator
+                    .address
+                    .parse::<Pubkey>()
+                    .map_err(|_| format!("Invalid `creators` address at index {}", i))?;
+                if parsed.iter().any(|c| c.address == address) {
+                    return Err(format!("Duplicate `creators` address at index {}", i));
+                }
+                if creator.verified && (address != *update_authority || !update_authority_signs) {
+                    return Err(format!(
+                        "Creator at index {} can only be verified by the signing update authority",
+                        i
+                    ));
+                }
+                parsed.push(Creator {
+                    address,
+                    verified: creator.verified,
+                    share: creator.share,
+                });
+            }
+            let total: u32 = parsed.iter().map(|c| c.share as u32).sum();
+            if total != 100 {
+                return Err(format!("`creators` shares must add up to 100, got {}", total));
+            }
+            Some(parsed)
+        }
+    };
+
+    Ok(DataV2 {
+        name: input.name.clone(),
+        symbol: input.symbol.clone(),
+        uri: input.uri.clone(),
+        seller_fee_basis_points: input.seller_fee_basis_points,
+        creators,
+        collection: None,
+        uses: None,
+    })
+}
+
+fn metadata_response(ix: Instruction, metadata: &Pubkey) -> (StatusCode, Json<serde_json::Value>) {
+    InstructionResponse::new(&[ix])
+        .field("metadata_account", metadata.to_string())
+        .respond()
+}
@@ -1,4 +1,15 @@
 pub mod keypair;
 pub mod token;
 pub mod message;
-pub mod transfer;
\ No newline at end of file
+pub mod transfer;
+pub mod address;
+pub mod transaction;
+pub mod decode;
+pub mod compute_budget;
+pub mod memo;
+pub mod metadata;
+pub mod nonce;
+pub mod stake;
+pub mod lookup_table;
+pub mod rent;
+pub mod response;
\ No newline at end of file
@@ -0,0 +1,151 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::nonce::State as NonceState;
+use solana_program::system_instruction;
+use axum::http::StatusCode;
+
+use super::response::{error_response, instruction_response, InstructionResponse};
+use super::token::parse_address;
+use super::rent::{rent_exempt_minimum, RentInput};
+
+#[derive(Deserialize)]
+pub struct CreateNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub payer: String,
+    // Defaults to `payer`
+    pub authority: Option<String>,
+    // Defaults to the rent exempt minimum for a nonce account
+    pub lamports: Option<u64>,
+    pub rent: Option<RentInput>,
+}
+
+#[derive(Deserialize)]
+pub struct AdvanceNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    pub destination: String,
+    pub lamports: u64,
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeNonceRequest {
+    #[serde(rename = "nonceAccount")]
+    pub nonce_account: String,
+    pub authority: String,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: String,
+}
+
+pub async fn create_nonce_account(Json(payload): Json<CreateNonceRequest>) -> impl IntoResponse {
+    let nonce_account = match parse_address(&payload.nonce_account, "nonceAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let payer = match parse_address(&payload.payer, "payer") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match payload.authority.as_deref() {
+        None => payer,
+        Some(authority) => match parse_address(authority, "authority") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    // A nonce account below the rent exempt minimum fails initialization
+    let rent_exempt_lamports = match rent_exempt_minimum(payload.rent.as_ref(), NonceState::size()) {
+        Ok(lamports) => lamports,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let lamports = payload.lamports.unwrap_or(rent_exempt_lamports);
+    if lamports < rent_exempt_lamports {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "`lamports` must be at least the rent exempt minimum of {}",
+                rent_exempt_lamports
+            ),
+        );
+    }
+
+    // `create_account` followed by `initialize_nonce_account`
+    let instructions = system_instruction::create_nonce_account(&payer, &nonce_account, &authority, lamports);
+
+    InstructionResponse::new(&instructions)
+        .rent_exempt(rent_exempt_lamports, NonceState::size())
+        .respond()
+}
+
+pub async fn advance_nonce_account(Json(payload): Json<AdvanceNonceRequest>) -> impl IntoResponse {
+    let nonce_account = match parse_address(&payload.nonce_account, "nonceAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(system_instruction::advance_nonce_account(&nonce_account, &authority)),
+        "advance_nonce_account",
+    )
+}
+
+pub async fn withdraw_nonce_account(Json(payload): Json<WithdrawNonceRequest>) -> impl IntoResponse {
+    let nonce_account = match parse_address(&payload.nonce_account, "nonceAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let destination = match parse_address(&payload.destination, "destination") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if payload.lamports == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+
+    instruction_response(
+        Ok(system_instruction::withdraw_nonce_account(
+            &nonce_account,
+            &authority,
+            &destination,
+            payload.lamports,
+        )),
+        "withdraw_nonce_account",
+    )
+}
+
+pub async fn authorize_nonce_account(Json(payload): Json<AuthorizeNonceRequest>) -> impl IntoResponse {
+    let nonce_account = match parse_address(&payload.nonce_account, "nonceAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let new_authority = match parse_address(&payload.new_authority, "newAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(system_instruction::authorize_nonce_account(&nonce_account, &authority, &new_authority)),
+        "authorize_nonce_account",
+    )
+}
@@ -0,0 +1,76 @@
+use serde::Deserialize;
+use solana_program::rent::{Rent, ACCOUNT_STORAGE_OVERHEAD};
+
+// Rent parameters for rent-exemption calculations, defaulting to the cluster values
+#[derive(Deserialize)]
+pub struct RentInput {
+    #[serde(rename = "lamportsPerByteYear")]
+    pub lamports_per_byte_year: u64,
+    #[serde(rename = "exemptionThreshold")]
+    pub exemption_threshold: f64,
+}
+
+// Rent exempt minimum for an account holding `space` bytes. Same formula as
+// `Rent::minimum_balance`, but client supplied parameters that overflow are an
+// error instead of a panic or a wrapped amount.
+pub fn rent_exempt_minimum(input: Option<&RentInput>, space: usize) -> Result<u64, String> {
+    let rent = match input {
+        None => Rent::default(),
+        Some(input) => {
+            if !input.exemption_threshold.is_finite() || input.exemption_threshold < 0.0 {
+                return Err("`exemptionThreshold` must be a non-negative number".to_string());
+            }
+            Rent {
+                lamports_per_byte_year: input.lamports_per_byte_year,
+                exemption_threshold: input.exemption_threshold,
+                ..Rent::default()
+            }
+        }
+    };
+
+    let overflow = || "Rent exempt minimum overflows u64 with the given `rent` parameters".to_string();
+    let bytes = (space as u64).checked_add(ACCOUNT_STORAGE_OVERHEAD).ok_or_else(overflow)?;
+    let lamports_per_year = bytes.checked_mul(rent.lamports_per_byte_year).ok_or_else(overflow)?;
+    let lamports = lamports_per_year as f64 * rent.exemption_threshold;
+    // u64::MAX rounds up to 2^64 as f64, so anything at or above it is out of range
+    if lamports >= u64::MAX as f64 {
+        return Err(overflow());
+    }
+    Ok(lamports as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_cluster_rent_by_default() {
+        for space in [0, 82, 165, 355] {
+            assert_eq!(rent_exempt_minimum(None, space), Ok(Rent::default().minimum_balance(space)));
+        }
+        assert_eq!(rent_exempt_minimum(None, 165), Ok(2_039_280));
+    }
+
+    #[test]
+    fn applies_custom_parameters() {
+        let input = RentInput { lamports_per_byte_year: 1_000, exemption_threshold: 1.0 };
+        assert_eq!(rent_exempt_minimum(Some(&input), 72), Ok(200_000));
+    }
+
+    #[test]
+    fn rejects_overflowing_parameters() {
+        let input = RentInput { lamports_per_byte_year: u64::MAX, exemption_threshold: 2.0 };
+        assert!(rent_exempt_minimum(Some(&input), 82).is_err());
+
+        let input = RentInput { lamports_per_byte_year: u64::MAX / 128, exemption_threshold: 1e12 };
+        assert!(rent_exempt_minimum(Some(&input), 0).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_threshold() {
+        for threshold in [-1.0, f64::NAN, f64::INFINITY] {
+            let input = RentInput { lamports_per_byte_year: 3_480, exemption_threshold: threshold };
+            assert!(rent_exempt_minimum(Some(&input), 0).is_err());
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use axum::Json;
+use axum::http::StatusCode;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use solana_program::instruction::Instruction;
+use solana_program::program_error::ProgramError;
+use base64::engine::general_purpose::STANDARD as base64_engine;
+use base64::Engine;
+
+// Builder output: the main instruction at the top level, plus everything to submit
+#[derive(Serialize)]
+pub struct InstructionResponse {
+    pub program_id: String,
+    pub accounts: Vec<Value>,
+    pub instruction_data: String,
+    // Every instruction to submit, in order, ending with the one above
+    pub instructions: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_lamports: Option<u64>,
+    // Bytes to allocate for the created account, including extensions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_size: Option<usize>,
+    // Endpoint specific fields such as derived addresses
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl InstructionResponse {
+    // `instructions` in submission order; the last one is the main instruction
+    pub fn new(instructions: &[Instruction]) -> Self {
+        let ix = instructions.last().expect("at least one instruction");
+        InstructionResponse {
+            program_id: ix.program_id.to_string(),
+            accounts: accounts_json(ix),
+            instruction_data: base64_engine.encode(&ix.data),
+            instructions: instructions.iter().map(instruction_json).collect(),
+            priority_fee_lamports: None,
+            rent_exempt_lamports: None,
+            account_size: None,
+            extra: Map::new(),
+        }
+    }
+
+    pub fn priority_fee(mut self, lamports: Option<u64>) -> Self {
+        self.priority_fee_lamports = lamports;
+        self
+    }
+
+    pub fn rent_exempt(mut self, lamports: u64, account_size: usize) -> Self {
+        self.rent_exempt_lamports = Some(lamports);
+        self.account_size = Some(account_size);
+        self
+    }
+
+    pub fn field(mut self, key: &str, value: impl Serialize) -> Self {
+        self.extra.insert(key.to_string(), json!(value));
+        self
+    }
+
+    pub fn respond(self) -> (StatusCode, Json<Value>) {
+        (StatusCode::OK, Json(json!({
+            "success": true,
+            "data": self
+        })))
+    }
+}
+
+pub fn error_response(status: StatusCode, error: String) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({
+        "success": false,
+        "error": error
+    })))
+}
+
+// Respond with a single instruction, or the error from building it
+pub fn instruction_response(ix: Result<Instruction, ProgramError>, name: &str) -> (StatusCode, Json<Value>) {
+    match ix {
+        Ok(ix) => InstructionResponse::new(&[ix]).respond(),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to create {} instruction: {}", name, e),
+        ),
+    }
+}
+
+// Serialize an instruction in the same shape as the single-instruction responses
+fn instruction_json(ix: &Instruction) -> Value {
+    json!({
+        "program_id": ix.program_id.to_string(),
+        "accounts": accounts_json(ix),
+        "instruction_data": base64_engine.encode(&ix.data),
+    })
+}
+
+fn accounts_json(ix: &Instruction) -> Vec<Value> {
+    ix.accounts.iter().map(|acct| json!({
+        "pubkey": acct.pubkey.to_string(),
+        "is_signer": acct.is_signer,
+        "is_writable": acct.is_writable
+    })).collect()
+}
+
+// Status and JSON body of a handler response, for handler tests
+#[cfg(test)]
+pub async fn response_json(response: impl axum::response::IntoResponse) -> (StatusCode, Value) {
+    use axum::body::HttpBody;
+
+    let mut response = response.into_response();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.body_mut().data().await {
+        body.extend_from_slice(&chunk.unwrap());
+    }
+    (response.status(), serde_json::from_slice(&body).unwrap())
+}
@@ -0,0 +1,411 @@
+use axum::{Json, response::IntoResponse};
+use serde::Deserialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::stake::{self, instruction as stake_instruction};
+use solana_program::stake::state::{Authorized, Lockup, StakeAuthorize, StakeStateV2};
+use solana_program::system_instruction;
+use axum::http::StatusCode;
+
+use super::response::{error_response, instruction_response, InstructionResponse};
+use super::token::parse_address;
+use super::rent::{rent_exempt_minimum, RentInput};
+
+#[derive(Deserialize)]
+pub struct CreateStakeRequest {
+    pub from: String,
+    // Required unless `seed` is given, in which case it is derived
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: Option<String>,
+    // Derive the stake account from `base` (defaults to `from`) and `seed`
+    pub seed: Option<String>,
+    pub base: Option<String>,
+    pub staker: String,
+    // Defaults to `staker`
+    pub withdrawer: Option<String>,
+    pub lockup: Option<LockupInput>,
+    // Total funding, including the rent exempt reserve
+    pub lamports: u64,
+    pub rent: Option<RentInput>,
+}
+
+#[derive(Deserialize)]
+pub struct LockupInput {
+    // Withdrawals are locked until both this time and `epoch` have passed
+    #[serde(rename = "unixTimestamp", default)]
+    pub unix_timestamp: i64,
+    #[serde(default)]
+    pub epoch: u64,
+    // May sign to withdraw or change the lockup before it expires; without one
+    // the lockup cannot be lifted early
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DelegateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+    #[serde(rename = "voteAccount")]
+    pub vote_account: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeactivateStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub withdrawer: String,
+    pub destination: String,
+    pub lamports: u64,
+    // Required to withdraw while the lockup is in force
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SplitStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    pub staker: String,
+    pub lamports: u64,
+    // A new keypair account that receives the split stake
+    #[serde(rename = "splitStakeAccount")]
+    pub split_stake_account: String,
+    // Prefunds the split account with its rent exempt reserve, which the stake
+    // program requires of split destinations
+    pub payer: Option<String>,
+    pub rent: Option<RentInput>,
+}
+
+#[derive(Deserialize)]
+pub struct MergeStakeRequest {
+    #[serde(rename = "destinationStakeAccount")]
+    pub destination_stake_account: String,
+    #[serde(rename = "sourceStakeAccount")]
+    pub source_stake_account: String,
+    pub staker: String,
+}
+
+#[derive(Deserialize)]
+pub struct AuthorizeStakeRequest {
+    #[serde(rename = "stakeAccount")]
+    pub stake_account: String,
+    // The current staker or withdrawer
+    pub authority: String,
+    #[serde(rename = "newAuthority")]
+    pub new_authority: String,
+    // "staker" or "withdrawer"
+    #[serde(rename = "authorityType")]
+    pub authority_type: String,
+    pub custodian: Option<String>,
+}
+
+pub async fn create_stake_account(Json(payload): Json<CreateStakeRequest>) -> impl IntoResponse {
+    let from = match parse_address(&payload.from, "from") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let staker = match parse_address(&payload.staker, "staker") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let withdrawer = match payload.withdrawer.as_deref() {
+        None => staker,
+        Some(withdrawer) => match parse_address(withdrawer, "withdrawer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    let lockup = match payload.lockup.as_ref() {
+        None => Lockup::default(),
+        Some(lockup) => match parse_lockup(lockup) {
+            Ok(lockup) => lockup,
+            Err(response) => return response,
+        },
+    };
+    let authorized = Authorized { staker, withdrawer };
+
+    let rent_exempt_lamports = match rent_exempt_minimum(payload.rent.as_ref(), StakeStateV2::size_of()) {
+        Ok(lamports) => lamports,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    if payload.lamports <= rent_exempt_lamports {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "`lamports` must exceed the rent exempt reserve of {}",
+                rent_exempt_lamports
+            ),
+        );
+    }
+
+    // `create_account` (or its seeded form) followed by `initialize`
+    let (stake_account, instructions) = match payload.seed.as_deref() {
+        None => {
+            let stake_account = match payload.stake_account.as_deref() {
+                Some(stake_account) => match parse_address(stake_account, "stakeAccount") {
+                    Ok(pk) => pk,
+                    Err(response) => return response,
+                },
+                None => {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        "Missing required field `stakeAccount` or `seed`".to_string(),
+                    );
+                }
+            };
+            let instructions =
+                stake_instruction::create_account(&from, &stake_account, &authorized, &lockup, payload.lamports);
+            (stake_account, instructions)
+        }
+        Some(seed) => {
+            let base = match payload.base.as_deref() {
+                None => from,
+                Some(base) => match parse_address(base, "base") {
+                    Ok(pk) => pk,
+                    Err(response) => return response,
+                },
+            };
+            let stake_account = match Pubkey::create_with_seed(&base, seed, &stake::program::id()) {
+                Ok(address) => address,
+                Err(e) => {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("Failed to derive address with seed: {}", e),
+                    );
+                }
+            };
+            // A given address must agree with the derived one
+            if let Some(given) = payload.stake_account.as_deref() {
+                if given != stake_account.to_string() {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("`stakeAccount` does not match the seed derived address {}", stake_account),
+                    );
+                }
+            }
+            let instructions = stake_instruction::create_account_with_seed(
+                &from,
+                &stake_account,
+                &base,
+                seed,
+                &authorized,
+                &lockup,
+                payload.lamports,
+            );
+            (stake_account, instructions)
+        }
+    };
+
+    InstructionResponse::new(&instructions)
+        .rent_exempt(rent_exempt_lamports, StakeStateV2::size_of())
+        .field("stake_account", stake_account.to_string())
+        .respond()
+}
+
+pub async fn delegate_stake(Json(payload): Json<DelegateStakeRequest>) -> impl IntoResponse {
+    let stake_account = match parse_address(&payload.stake_account, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let staker = match parse_address(&payload.staker, "staker") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let vote_account = match parse_address(&payload.vote_account, "voteAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(stake_instruction::delegate_stake(&stake_account, &staker, &vote_account)),
+        "delegate_stake",
+    )
+}
+
+pub async fn deactivate_stake(Json(payload): Json<DeactivateStakeRequest>) -> impl IntoResponse {
+    let stake_account = match parse_address(&payload.stake_account, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let staker = match parse_address(&payload.staker, "staker") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        Ok(stake_instruction::deactivate_stake(&stake_account, &staker)),
+        "deactivate_stake",
+    )
+}
+
+pub async fn withdraw_stake(Json(payload): Json<WithdrawStakeRequest>) -> impl IntoResponse {
+    let stake_account = match parse_address(&payload.stake_account, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let withdrawer = match parse_address(&payload.withdrawer, "withdrawer") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let destination = match parse_address(&payload.destination, "destination") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let custodian = match payload.custodian.as_deref() {
+        None => None,
+        Some(custodian) => match parse_address(custodian, "custodian") {
+            Ok(pk) => Some(pk),
+            Err(response) => return response,
+        },
+    };
+    if payload.lamports == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+
+    instruction_response(
+        Ok(stake_instruction::withdraw(
+            &stake_account,
+            &withdrawer,
+            &destination,
+            payload.lamports,
+            custodian.as_ref(),
+        )),
+        "withdraw",
+    )
+}
+
+pub async fn split_stake(Json(payload): Json<SplitStakeRequest>) -> impl IntoResponse {
+    let stake_account = match parse_address(&payload.stake_account, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let staker = match parse_address(&payload.staker, "staker") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let split_stake_account = match parse_address(&payload.split_stake_account, "splitStakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if split_stake_account == stake_account {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "`splitStakeAccount` must differ from `stakeAccount`".to_string(),
+        );
+    }
+    if payload.lamports == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+
+    let mut instructions = Vec::with_capacity(4);
+    if let Some(payer) = payload.payer.as_deref() {
+        let payer = match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        };
+        let rent_exempt_lamports = match rent_exempt_minimum(payload.rent.as_ref(), StakeStateV2::size_of()) {
+            Ok(lamports) => lamports,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        };
+        instructions.push(system_instruction::transfer(&payer, &split_stake_account, rent_exempt_lamports));
+    }
+    // `allocate` and `assign` the new account, then `split` into it
+    instructions.extend(stake_instruction::split(
+        &stake_account,
+        &staker,
+        payload.lamports,
+        &split_stake_account,
+    ));
+
+    InstructionResponse::new(&instructions).respond()
+}
+
+pub async fn merge_stake(Json(payload): Json<MergeStakeRequest>) -> impl IntoResponse {
+    let destination = match parse_address(&payload.destination_stake_account, "destinationStakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let source = match parse_address(&payload.source_stake_account, "sourceStakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let staker = match parse_address(&payload.staker, "staker") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if destination == source {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "`sourceStakeAccount` must differ from `destinationStakeAccount`".to_string(),
+        );
+    }
+
+    InstructionResponse::new(&stake_instruction::merge(&destination, &source, &staker)).respond()
+}
+
+pub async fn authorize_stake(Json(payload): Json<AuthorizeStakeRequest>) -> impl IntoResponse {
+    let stake_account = match parse_address(&payload.stake_account, "stakeAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let authority = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let new_authority = match parse_address(&payload.new_authority, "newAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let custodian = match payload.custodian.as_deref() {
+        None => None,
+        Some(custodian) => match parse_address(custodian, "custodian") {
+            Ok(pk) => Some(pk),
+            Err(response) => return response,
+        },
+    };
+    let stake_authorize = match payload.authority_type.as_str() {
+        "staker" => StakeAuthorize::Staker,
+        "withdrawer" => StakeAuthorize::Withdrawer,
+        other => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown `authorityType` `{}`, expected staker or withdrawer", other),
+            );
+        }
+    };
+
+    instruction_response(
+        Ok(stake_instruction::authorize(
+            &stake_account,
+            &authority,
+            &new_authority,
+            stake_authorize,
+            custodian.as_ref(),
+        )),
+        "authorize",
+    )
+}
+
+fn parse_lockup(input: &LockupInput) -> Result<Lockup, (StatusCode, Json<serde_json::Value>)> {
+    if input.unix_timestamp < 0 {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "`lockup.unixTimestamp` must not be negative".to_string(),
+        ));
+    }
+    let custodian = match input.custodian.as_deref() {
+        None => Pubkey::default(),
+        Some(custodian) => parse_address(custodian, "lockup.custodian")?,
+    };
+    Ok(Lockup {
+        unix_timestamp: input.unix_timestamp,
+        epoch: input.epoch,
+        custodian,
+    })
+}
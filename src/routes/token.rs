@@ -1,94 +1,357 @@
 use axum::{Json, response::IntoResponse};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use solana_program::instruction::Instruction;
+use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
-use spl_token::instruction as token_instruction;
-use base64::engine::general_purpose::STANDARD as base64_engine;
-use base64::Engine;
-use serde_json::json;
+use solana_program::system_instruction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use spl_token_2022::extension::{default_account_state, interest_bearing_mint, metadata_pointer, transfer_fee, ExtensionType};
+use spl_token_2022::instruction::{self as token_instruction, AuthorityType, MAX_SIGNERS};
+use spl_token_2022::state::{AccountState, Mint, Multisig};
 use axum::http::StatusCode;
-use ed25519_dalek::Keypair;
-use bs58;
-use solana_sdk::signature::read_keypair_file;
+
+use super::compute_budget::compute_budget_instructions;
+use super::response::{error_response, instruction_response, InstructionResponse};
+use super::rent::{rent_exempt_minimum, RentInput};
 
 #[derive(Deserialize)]
 pub struct CreateTokenRequest {
-    pub mintAuthority: String,
+    #[serde(rename = "mintAuthority")]
+    pub mint_authority: String,
     pub mint: String,
     pub decimals: u8,
+    #[serde(rename = "freezeAuthority")]
+    pub freeze_authority: Option<String>,
+    // "initialize_mint" (default, reads the rent sysvar) or "initialize_mint2"
+    #[serde(rename = "initializeInstruction")]
+    pub initialize_instruction: Option<String>,
+    // Also emit the system `create_account` for the mint, funded by `payer`
+    #[serde(rename = "createAccount", default)]
+    pub create_account: bool,
+    // Defaults to `mintAuthority`
+    pub payer: Option<String>,
+    pub rent: Option<RentInput>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    pub program: Option<String>,
+    // Token-2022 mint extensions, initialized ahead of the mint
+    pub extensions: Option<MintExtensionsInput>,
+}
+
+#[derive(Deserialize)]
+pub struct MintExtensionsInput {
+    #[serde(rename = "transferFee")]
+    pub transfer_fee: Option<TransferFeeInput>,
+    #[serde(rename = "interestBearing")]
+    pub interest_bearing: Option<InterestBearingInput>,
+    #[serde(rename = "nonTransferable", default)]
+    pub non_transferable: bool,
+    #[serde(rename = "permanentDelegate")]
+    pub permanent_delegate: Option<String>,
+    // "initialized" or "frozen"
+    #[serde(rename = "defaultAccountState")]
+    pub default_account_state: Option<String>,
+    #[serde(rename = "mintCloseAuthority")]
+    pub mint_close_authority: Option<String>,
+    #[serde(rename = "metadataPointer")]
+    pub metadata_pointer: Option<MetadataPointerInput>,
+}
+
+#[derive(Deserialize)]
+pub struct TransferFeeInput {
+    #[serde(rename = "feeBasisPoints")]
+    pub fee_basis_points: u16,
+    // Largest fee charged on a single transfer, in base units
+    #[serde(rename = "maximumFee")]
+    pub maximum_fee: u64,
+    #[serde(rename = "transferFeeConfigAuthority")]
+    pub transfer_fee_config_authority: Option<String>,
+    #[serde(rename = "withdrawWithheldAuthority")]
+    pub withdraw_withheld_authority: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct InterestBearingInput {
+    // Annual rate in basis points, may be negative
+    pub rate: i16,
+    #[serde(rename = "rateAuthority")]
+    pub rate_authority: Option<String>,
 }
 
-#[derive(Serialize)]
-pub struct TokenResponse {
-    pub program_id: String,
-    pub accounts: Vec<serde_json::Value>,
-    pub instruction_data: String,
+#[derive(Deserialize)]
+pub struct MetadataPointerInput {
+    pub authority: Option<String>,
+    #[serde(rename = "metadataAddress")]
+    pub metadata_address: Option<String>,
 }
 
 pub async fn create_token(Json(payload): Json<CreateTokenRequest>) -> impl IntoResponse {
+    // Select the token program
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
     // Parse mint public key
-    let mint_pubkey = match payload.mint.parse::<Pubkey>() {
+    let mint_pubkey = match parse_address(&payload.mint, "mint") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `mint` address"
-                })),
-            );
-        }
+        Err(response) => return response,
     };
 
     // Parse authority public key
-    let authority = match payload.mintAuthority.parse::<Pubkey>() {
+    let authority = match parse_address(&payload.mint_authority, "mintAuthority") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
+        Err(response) => return response,
+    };
+
+    // Parse optional freeze authority public key
+    let freeze_authority = match payload.freeze_authority.as_deref() {
+        None => None,
+        Some(freeze_authority) => match parse_address(freeze_authority, "freezeAuthority") {
+            Ok(pk) => Some(pk),
+            Err(response) => return response,
+        },
+    };
+
+    // Parse optional payer public key
+    let payer = match payload.payer.as_deref() {
+        None => authority,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    // Create initialize mint instruction
+    let ix = match payload.initialize_instruction.as_deref().unwrap_or("initialize_mint") {
+        "initialize_mint" => token_instruction::initialize_mint(
+            &token_program,
+            &mint_pubkey,
+            &authority,
+            freeze_authority.as_ref(),
+            payload.decimals,
+        ),
+        "initialize_mint2" => token_instruction::initialize_mint2(
+            &token_program,
+            &mint_pubkey,
+            &authority,
+            freeze_authority.as_ref(),
+            payload.decimals,
+        ),
+        other => {
+            return error_response(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `mintAuthority` address"
-                })),
+                format!(
+                    "Unknown `initializeInstruction` `{}`, expected `initialize_mint` or `initialize_mint2`",
+                    other
+                ),
             );
         }
     };
-
-    // Create initialize mint instruction
-    let ix: Instruction = match token_instruction::initialize_mint(
-        &spl_token::id(),
-        &mint_pubkey,
-        &authority,
-        None,
-        payload.decimals,
-    ) {
+    let ix: Instruction = match ix {
         Ok(instruction) => instruction,
         Err(e) => {
-            return (
+            return error_response(
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "success": false,
-                    "error": format!("Failed to create initialize mint instruction: {}", e)
-                })),
+                format!("Failed to create initialize mint instruction: {}", e),
             );
         }
     };
 
-    let data = base64_engine.encode(&ix.data);
-    let accounts = ix.accounts.iter().map(|acct| serde_json::json!({
-        "pubkey": acct.pubkey.to_string(),
-        "is_signer": acct.is_signer,
-        "is_writable": acct.is_writable
-    })).collect::<Vec<_>>();
+    // Extensions are initialized on the allocated mint before `initialize_mint`
+    let (extension_types, extension_instructions) = match payload.extensions.as_ref() {
+        None => (Vec::new(), Vec::new()),
+        Some(_) if token_program != spl_token_2022::id() => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "`extensions` require `program` `token-2022`".to_string(),
+            );
+        }
+        Some(extensions) => match mint_extension_instructions(
+            &token_program,
+            &mint_pubkey,
+            extensions,
+            freeze_authority.as_ref(),
+        ) {
+            Ok(extensions) => extensions,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        },
+    };
+    let account_size = match ExtensionType::try_calculate_account_len::<Mint>(&extension_types) {
+        Ok(size) => size,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to size the mint account: {}", e),
+            );
+        }
+    };
 
-    (StatusCode::OK, Json(serde_json::json!({
-        "success": true,
-        "data": TokenResponse {
-            program_id: ix.program_id.to_string(),
-            accounts,
-            instruction_data: data,
+    // Optional compute budget instructions run ahead of everything else
+    let instruction_count =
+        1 + payload.create_account as u32 + extension_instructions.len() as u32;
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        instruction_count,
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    // The mint account must exist, rent exempt and owned by the token program, before initialization
+    let mut rent_exempt_lamports = None;
+    if payload.create_account {
+        let lamports = match rent_exempt_minimum(payload.rent.as_ref(), account_size) {
+            Ok(lamports) => lamports,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        };
+        instructions.push(system_instruction::create_account(
+            &payer,
+            &mint_pubkey,
+            lamports,
+            account_size as u64,
+            &token_program,
+        ));
+        rent_exempt_lamports = Some(lamports);
+    }
+    instructions.extend(extension_instructions);
+    instructions.push(ix);
+
+    let mut response = InstructionResponse::new(&instructions).priority_fee(priority_fee);
+    response.rent_exempt_lamports = rent_exempt_lamports;
+    response.account_size = Some(account_size);
+    response.respond()
+}
+
+// Build the Token-2022 extension initialization instructions for a new mint,
+// along with the extension types that size its account
+fn mint_extension_instructions(
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    extensions: &MintExtensionsInput,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<(Vec<ExtensionType>, Vec<Instruction>), String> {
+    let parse = |value: &str, field: &str| {
+        value
+            .parse::<Pubkey>()
+            .map_err(|_| format!("Invalid `{}` address", field))
+    };
+    let parse_optional = |value: Option<&str>, field: &str| value.map(|v| parse(v, field)).transpose();
+    let failed = |name: &str, e: solana_program::program_error::ProgramError| {
+        format!("Failed to create {} instruction: {}", name, e)
+    };
+
+    let mut types = Vec::new();
+    let mut instructions = Vec::new();
+
+    if let Some(fee) = extensions.transfer_fee.as_ref() {
+        if fee.fee_basis_points > MAX_FEE_BASIS_POINTS {
+            return Err(format!("`feeBasisPoints` must be at most {}", MAX_FEE_BASIS_POINTS));
         }
-    })))
+        let config_authority =
+            parse_optional(fee.transfer_fee_config_authority.as_deref(), "transferFeeConfigAuthority")?;
+        let withdraw_authority =
+            parse_optional(fee.withdraw_withheld_authority.as_deref(), "withdrawWithheldAuthority")?;
+        types.push(ExtensionType::TransferFeeConfig);
+        instructions.push(
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program,
+                mint,
+                config_authority.as_ref(),
+                withdraw_authority.as_ref(),
+                fee.fee_basis_points,
+                fee.maximum_fee,
+            )
+            .map_err(|e| failed("initialize_transfer_fee_config", e))?,
+        );
+    }
+
+    if let Some(interest) = extensions.interest_bearing.as_ref() {
+        let rate_authority = parse_optional(interest.rate_authority.as_deref(), "rateAuthority")?;
+        types.push(ExtensionType::InterestBearingConfig);
+        instructions.push(
+            interest_bearing_mint::instruction::initialize(token_program, mint, rate_authority, interest.rate)
+                .map_err(|e| failed("initialize_interest_bearing_mint", e))?,
+        );
+    }
+
+    if extensions.non_transferable {
+        types.push(ExtensionType::NonTransferable);
+        instructions.push(
+            token_instruction::initialize_non_transferable_mint(token_program, mint)
+                .map_err(|e| failed("initialize_non_transferable_mint", e))?,
+        );
+    }
+
+    if let Some(delegate) = extensions.permanent_delegate.as_deref() {
+        let delegate = parse(delegate, "permanentDelegate")?;
+        types.push(ExtensionType::PermanentDelegate);
+        instructions.push(
+            token_instruction::initialize_permanent_delegate(token_program, mint, &delegate)
+                .map_err(|e| failed("initialize_permanent_delegate", e))?,
+        );
+    }
+
+    if let Some(state) = extensions.default_account_state.as_deref() {
+        let state = match state {
+            "initialized" => AccountState::Initialized,
+            // Frozen accounts could never be thawed without a freeze authority
+            "frozen" if freeze_authority.is_none() => {
+                return Err("`defaultAccountState` `frozen` requires `freezeAuthority`".to_string());
+            }
+            "frozen" => AccountState::Frozen,
+            other => {
+                return Err(format!(
+                    "Unknown `defaultAccountState` `{}`, expected `initialized` or `frozen`",
+                    other
+                ));
+            }
+        };
+        types.push(ExtensionType::DefaultAccountState);
+        instructions.push(
+            default_account_state::instruction::initialize_default_account_state(token_program, mint, &state)
+                .map_err(|e| failed("initialize_default_account_state", e))?,
+        );
+    }
+
+    if let Some(close_authority) = extensions.mint_close_authority.as_deref() {
+        let close_authority = parse(close_authority, "mintCloseAuthority")?;
+        types.push(ExtensionType::MintCloseAuthority);
+        instructions.push(
+            token_instruction::initialize_mint_close_authority(token_program, mint, Some(&close_authority))
+                .map_err(|e| failed("initialize_mint_close_authority", e))?,
+        );
+    }
+
+    if let Some(pointer) = extensions.metadata_pointer.as_ref() {
+        let authority = parse_optional(pointer.authority.as_deref(), "authority")?;
+        let metadata_address = parse_optional(pointer.metadata_address.as_deref(), "metadataAddress")?;
+        if authority.is_none() && metadata_address.is_none() {
+            return Err("`metadataPointer` needs an `authority` or a `metadataAddress`".to_string());
+        }
+        types.push(ExtensionType::MetadataPointer);
+        instructions.push(
+            metadata_pointer::instruction::initialize(token_program, mint, authority, metadata_address)
+                .map_err(|e| failed("initialize_metadata_pointer", e))?,
+        );
+    }
+
+    Ok((types, instructions))
+}
+
+// Options shared by the requests whose authority may be an SPL multisig account
+#[derive(Deserialize)]
+pub struct AuthorityOptions {
+    // The multisig's signing members when the authority is a multisig, otherwise empty
+    #[serde(default)]
+    pub signers: Vec<String>,
+    // Token program, see `token_program_id`
+    pub program: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -96,86 +359,910 @@ pub struct MintTokenRequest {
     pub mint: String,
     pub destination: String,
     pub authority: String,
-    pub amount: u64,
+    // Base units; alternatively `uiAmount` with `decimals`
+    pub amount: Option<u64>,
+    #[serde(rename = "uiAmount")]
+    pub ui_amount: Option<String>,
+    // When set, emits `mint_to_checked` against the mint's decimals
+    pub decimals: Option<u8>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
 }
 
 pub async fn mint_token(Json(payload): Json<MintTokenRequest>) -> impl IntoResponse {
+    // Select the token program
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
     // Parse mint public key
-    let mint = match payload.mint.parse::<Pubkey>() {
+    let mint = match parse_address(&payload.mint, "mint") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `mint` address"
-                })),
+        Err(response) => return response,
+    };
+
+    // Parse destination public key
+    let dest = match parse_address(&payload.destination, "destination") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    // Parse authority public key
+    let auth = match parse_address(&payload.authority, "authority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    // Resolve the amount in base units
+    let amount = match resolve_amount(payload.amount, payload.ui_amount.as_deref(), payload.decimals) {
+        Ok(amount) => amount,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    // Parse multisig signer public keys
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    // Create mint to instruction, checked against the mint decimals when given
+    let ix = match payload.decimals {
+        None => token_instruction::mint_to(&token_program, &mint, &dest, &auth, &signer_refs, amount),
+        Some(decimals) => token_instruction::mint_to_checked(
+            &token_program,
+            &mint,
+            &dest,
+            &auth,
+            &signer_refs,
+            amount,
+            decimals,
+        ),
+    };
+    let ix = match ix {
+        Ok(instruction) => instruction,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to create mint_to instruction: {}", e),
             );
         }
     };
 
-    // Parse destination public key
-    let dest = match payload.destination.parse::<Pubkey>() {
+    // Optional compute budget instructions run ahead of the main instruction
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        1,
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    instructions.push(ix);
+
+    InstructionResponse::new(&instructions).priority_fee(priority_fee).respond()
+}
+
+#[derive(Deserialize)]
+pub struct InitializeAccountRequest {
+    pub account: String,
+    pub mint: String,
+    pub owner: String,
+    pub program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BurnTokenRequest {
+    pub account: String,
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+    // When set, emits `burn_checked`
+    pub decimals: Option<u8>,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct ApproveRequest {
+    pub source: String,
+    pub delegate: String,
+    pub owner: String,
+    pub amount: u64,
+    // Required together with `decimals` for `approve_checked`
+    pub mint: Option<String>,
+    pub decimals: Option<u8>,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct RevokeRequest {
+    pub source: String,
+    pub owner: String,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct SetAuthorityRequest {
+    // The mint or token account whose authority changes
+    pub account: String,
+    #[serde(rename = "currentAuthority")]
+    pub current_authority: String,
+    // mintTokens, freezeAccount, accountOwner or closeAccount, plus the Token-2022
+    // transferFeeConfig, withheldWithdraw, closeMint, interestRate, permanentDelegate
+    // and metadataPointer
+    #[serde(rename = "authorityType")]
+    pub authority_type: String,
+    // `null` removes the authority
+    #[serde(rename = "newAuthority")]
+    pub new_authority: Option<String>,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct FreezeRequest {
+    pub account: String,
+    pub mint: String,
+    #[serde(rename = "freezeAuthority")]
+    pub freeze_authority: String,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct CloseAccountRequest {
+    pub account: String,
+    pub destination: String,
+    pub owner: String,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
+}
+
+#[derive(Deserialize)]
+pub struct InitializeMultisigRequest {
+    pub multisig: String,
+    // The n signer addresses, any `m` of which can act for the multisig
+    pub signers: Vec<String>,
+    pub m: u8,
+    // "initialize_multisig" (default, reads the rent sysvar) or "initialize_multisig2"
+    #[serde(rename = "initializeInstruction")]
+    pub initialize_instruction: Option<String>,
+    // Also emit the system `create_account` for the multisig, funded by `payer`
+    #[serde(rename = "createAccount", default)]
+    pub create_account: bool,
+    pub payer: Option<String>,
+    pub rent: Option<RentInput>,
+    pub program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SyncNativeRequest {
+    pub account: String,
+    pub program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct WrapRequest {
+    pub wallet: String,
+    pub lamports: u64,
+    // Funds the associated token account creation, defaults to `wallet`
+    pub payer: Option<String>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    pub program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UnwrapRequest {
+    pub wallet: String,
+    // Receives the unwrapped lamports, defaults to `wallet`
+    pub destination: Option<String>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    pub program: Option<String>,
+}
+
+pub async fn initialize_account(Json(payload): Json<InitializeAccountRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
+        Err(response) => return response,
+    };
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        token_instruction::initialize_account3(&token_program, &account, &mint, &owner),
+        "initialize_account3",
+    )
+}
+
+pub async fn burn_token(Json(payload): Json<BurnTokenRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if payload.amount == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    match payload.decimals {
+        None => instruction_response(
+            token_instruction::burn(&token_program, &account, &mint, &owner, &signer_refs, payload.amount),
+            "burn",
+        ),
+        Some(decimals) => instruction_response(
+            token_instruction::burn_checked(
+                &token_program,
+                &account,
+                &mint,
+                &owner,
+                &signer_refs,
+                payload.amount,
+                decimals,
+            ),
+            "burn_checked",
+        ),
+    }
+}
+
+pub async fn approve_delegate(Json(payload): Json<ApproveRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let source = match parse_address(&payload.source, "source") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let delegate = match parse_address(&payload.delegate, "delegate") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    match (payload.mint.as_deref(), payload.decimals) {
+        (None, None) => instruction_response(
+            token_instruction::approve(&token_program, &source, &delegate, &owner, &signer_refs, payload.amount),
+            "approve",
+        ),
+        (Some(mint), Some(decimals)) => {
+            let mint = match parse_address(mint, "mint") {
+                Ok(pk) => pk,
+                Err(response) => return response,
+            };
+            instruction_response(
+                token_instruction::approve_checked(
+                    &token_program,
+                    &source,
+                    &mint,
+                    &delegate,
+                    &owner,
+                    &signer_refs,
+                    payload.amount,
+                    decimals,
+                ),
+                "approve_checked",
+            )
+        }
+        _ => error_response(
+            StatusCode::BAD_REQUEST,
+            "`mint` and `decimals` must be provided together".to_string(),
+        ),
+    }
+}
+
+pub async fn revoke_delegate(Json(payload): Json<RevokeRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let source = match parse_address(&payload.source, "source") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    instruction_response(
+        token_instruction::revoke(&token_program, &source, &owner, &signer_refs),
+        "revoke",
+    )
+}
+
+pub async fn set_authority(Json(payload): Json<SetAuthorityRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let current_authority = match parse_address(&payload.current_authority, "currentAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let new_authority = match payload.new_authority.as_deref() {
+        None => None,
+        Some(new_authority) => match parse_address(new_authority, "newAuthority") {
+            Ok(pk) => Some(pk),
+            Err(response) => return response,
+        },
+    };
+    let authority_type = match payload.authority_type.as_str() {
+        "mintTokens" => AuthorityType::MintTokens,
+        "freezeAccount" => AuthorityType::FreezeAccount,
+        "accountOwner" => AuthorityType::AccountOwner,
+        "closeAccount" => AuthorityType::CloseAccount,
+        "transferFeeConfig" => AuthorityType::TransferFeeConfig,
+        "withheldWithdraw" => AuthorityType::WithheldWithdraw,
+        "closeMint" => AuthorityType::CloseMint,
+        "interestRate" => AuthorityType::InterestRate,
+        "permanentDelegate" => AuthorityType::PermanentDelegate,
+        "metadataPointer" => AuthorityType::MetadataPointer,
+        other => {
+            return error_response(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `destination` address"
-                })),
+                format!(
+                    "Unknown `authorityType` `{}`, expected mintTokens, freezeAccount, accountOwner, closeAccount, \
+                     transferFeeConfig, withheldWithdraw, closeMint, interestRate, permanentDelegate or metadataPointer",
+                    other
+                ),
             );
         }
     };
+    let is_extension_authority = !matches!(
+        authority_type,
+        AuthorityType::MintTokens
+            | AuthorityType::FreezeAccount
+            | AuthorityType::AccountOwner
+            | AuthorityType::CloseAccount
+    );
+    if is_extension_authority && token_program != spl_token_2022::id() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("`authorityType` `{}` requires `program` `token-2022`", payload.authority_type),
+        );
+    }
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
 
-    // Parse authority public key
-    let auth = match payload.authority.parse::<Pubkey>() {
+    instruction_response(
+        token_instruction::set_authority(
+            &token_program,
+            &account,
+            new_authority.as_ref(),
+            authority_type,
+            &current_authority,
+            &signer_refs,
+        ),
+        "set_authority",
+    )
+}
+
+pub async fn freeze_account(Json(payload): Json<FreezeRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let mint = match parse_address(&payload.mint, "mint") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
+        Err(response) => return response,
+    };
+    let freeze_authority = match parse_address(&payload.freeze_authority, "freezeAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    instruction_response(
+        token_instruction::freeze_account(&token_program, &account, &mint, &freeze_authority, &signer_refs),
+        "freeze_account",
+    )
+}
+
+pub async fn thaw_account(Json(payload): Json<FreezeRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let freeze_authority = match parse_address(&payload.freeze_authority, "freezeAuthority") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    instruction_response(
+        token_instruction::thaw_account(&token_program, &account, &mint, &freeze_authority, &signer_refs),
+        "thaw_account",
+    )
+}
+
+pub async fn close_account(Json(payload): Json<CloseAccountRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let destination = match parse_address(&payload.destination, "destination") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    instruction_response(
+        token_instruction::close_account(&token_program, &account, &destination, &owner, &signer_refs),
+        "close_account",
+    )
+}
+
+pub async fn initialize_multisig(Json(payload): Json<InitializeMultisigRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let multisig = match parse_address(&payload.multisig, "multisig") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let signers = match parse_signers(&payload.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    if signers.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "At least one signer is required".to_string());
+    }
+    if payload.m == 0 || payload.m as usize > signers.len() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("`m` must be between 1 and the number of signers ({})", signers.len()),
+        );
+    }
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    let ix = match payload.initialize_instruction.as_deref().unwrap_or("initialize_multisig") {
+        "initialize_multisig" => {
+            token_instruction::initialize_multisig(&token_program, &multisig, &signer_refs, payload.m)
+        }
+        "initialize_multisig2" => {
+            token_instruction::initialize_multisig2(&token_program, &multisig, &signer_refs, payload.m)
+        }
+        other => {
+            return error_response(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `authority` address"
-                })),
+                format!(
+                    "Unknown `initializeInstruction` `{}`, expected `initialize_multisig` or `initialize_multisig2`",
+                    other
+                ),
             );
         }
     };
+    if !payload.create_account {
+        return instruction_response(ix, "initialize_multisig");
+    }
 
-    // Create mint to instruction
-    let ix = match token_instruction::mint_to(
-        &spl_token::id(), 
-        &mint, 
-        &dest, 
-        &auth, 
-        &[], 
-        payload.amount
+    // The multisig account must exist, rent exempt and owned by the token program, before initialization
+    let ix = match ix {
+        Ok(instruction) => instruction,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to create initialize_multisig instruction: {}", e),
+            );
+        }
+    };
+    let payer = match payload.payer.as_deref() {
+        None => return error_response(StatusCode::BAD_REQUEST, "`createAccount` requires `payer`".to_string()),
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    let lamports = match rent_exempt_minimum(payload.rent.as_ref(), Multisig::LEN) {
+        Ok(lamports) => lamports,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let create_ix = system_instruction::create_account(
+        &payer,
+        &multisig,
+        lamports,
+        Multisig::LEN as u64,
+        &token_program,
+    );
+
+    InstructionResponse::new(&[create_ix, ix])
+        .rent_exempt(lamports, Multisig::LEN)
+        .respond()
+}
+
+pub async fn sync_native(Json(payload): Json<SyncNativeRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(
+        token_instruction::sync_native(&token_program, &account),
+        "sync_native",
+    )
+}
+
+pub async fn wrap_sol(Json(payload): Json<WrapRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let wallet = match parse_address(&payload.wallet, "wallet") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let payer = match payload.payer.as_deref() {
+        None => wallet,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    if payload.lamports == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+
+    let native_mint = native_mint_id(&token_program);
+    let token_account = get_associated_token_address_with_program_id(&wallet, &native_mint, &token_program);
+
+    // Create the wSOL account if needed, fund it, then sync its token amount with its lamports
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        3,
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    instructions.push(create_associated_token_account_idempotent(
+        &payer,
+        &wallet,
+        &native_mint,
+        &token_program,
+    ));
+    instructions.push(system_instruction::transfer(&wallet, &token_account, payload.lamports));
+    let ix = match token_instruction::sync_native(&token_program, &token_account) {
+        Ok(instruction) => instruction,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to create sync_native instruction: {}", e),
+            );
+        }
+    };
+    instructions.push(ix);
+
+    wrapped_sol_response(&instructions, &token_account, priority_fee)
+}
+
+pub async fn unwrap_sol(Json(payload): Json<UnwrapRequest>) -> impl IntoResponse {
+    let token_program = match token_program_id(payload.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let wallet = match parse_address(&payload.wallet, "wallet") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let destination = match payload.destination.as_deref() {
+        None => wallet,
+        Some(destination) => match parse_address(destination, "destination") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    let native_mint = native_mint_id(&token_program);
+    let token_account = get_associated_token_address_with_program_id(&wallet, &native_mint, &token_program);
+
+    // Closing the wSOL account returns its whole balance, rent included, as SOL
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        1,
     ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let ix = match token_instruction::close_account(&token_program, &token_account, &destination, &wallet, &[]) {
         Ok(instruction) => instruction,
         Err(e) => {
-            return (
+            return error_response(
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "success": false,
-                    "error": format!("Failed to create mint_to instruction: {}", e)
-                })),
+                format!("Failed to create close_account instruction: {}", e),
             );
         }
     };
+    instructions.push(ix);
+
+    wrapped_sol_response(&instructions, &token_account, priority_fee)
+}
+
+// The native mint of each token program
+fn native_mint_id(token_program: &Pubkey) -> Pubkey {
+    if *token_program == spl_token_2022::id() {
+        spl_token_2022::native_mint::id()
+    } else {
+        spl_token::native_mint::id()
+    }
+}
+
+fn wrapped_sol_response(
+    instructions: &[Instruction],
+    token_account: &Pubkey,
+    priority_fee: Option<u64>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    InstructionResponse::new(instructions)
+        .priority_fee(priority_fee)
+        .field("token_account", token_account.to_string())
+        .respond()
+}
+
+// Resolve a request amount to base units. Exactly one of `amount` (base
+// units) or `ui_amount` (a decimal string such as "12.5") must be given, and
+// a UI amount needs the mint's `decimals` to scale it.
+pub fn resolve_amount(
+    amount: Option<u64>,
+    ui_amount: Option<&str>,
+    decimals: Option<u8>,
+) -> Result<u64, String> {
+    match (amount, ui_amount) {
+        (Some(amount), None) => Ok(amount),
+        (None, Some(ui_amount)) => match decimals {
+            Some(decimals) => ui_amount_to_amount(ui_amount, decimals),
+            None => Err("`uiAmount` requires `decimals`".to_string()),
+        },
+        (Some(_), Some(_)) => Err("Provide either `amount` or `uiAmount`, not both".to_string()),
+        (None, None) => Err("Missing required field `amount`".to_string()),
+    }
+}
+
+// Convert a decimal string to base units without going through floating point
+pub fn ui_amount_to_amount(ui_amount: &str, decimals: u8) -> Result<u64, String> {
+    let invalid = || format!("Invalid `uiAmount` `{}`", ui_amount);
+    let (whole, fraction) = match ui_amount.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (ui_amount, ""),
+    };
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
 
-    let data = base64_engine.encode(&ix.data);
-    let accounts = ix.accounts.iter().map(|acct| serde_json::json!({
-        "pubkey": acct.pubkey.to_string(),
-        "is_signer": acct.is_signer,
-        "is_writable": acct.is_writable
-    })).collect::<Vec<_>>();
+    // Trailing zeros carry no precision, anything else past `decimals` would be lost
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(format!(
+            "`uiAmount` `{}` has more than {} decimal places",
+            ui_amount, decimals
+        ));
+    }
+
+    let overflow = || format!("`uiAmount` `{}` overflows a u64 amount", ui_amount);
+    let digits = format!("{}{}", whole, fraction);
+    let mut value: u128 = 0;
+    for digit in digits.bytes() {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((digit - b'0') as u128))
+            .ok_or_else(overflow)?;
+    }
+    // Zero stays zero at any scale, even one past u128
+    if value == 0 {
+        return Ok(0);
+    }
+    let scale = 10u128
+        .checked_pow((decimals as usize - fraction.len()) as u32)
+        .ok_or_else(overflow)?;
+    let value = value.checked_mul(scale).ok_or_else(overflow)?;
+    u64::try_from(value).map_err(|_| overflow())
+}
 
-    (StatusCode::OK, Json(serde_json::json!({
-        "success": true,
-        "data": TokenResponse {
-            program_id: ix.program_id.to_string(),
-            accounts,
-            instruction_data: data,
+// Resolve the `program` selector, "spl-token" (default) or "token-2022", to a token program id
+pub fn token_program_id(program: Option<&str>) -> Result<Pubkey, String> {
+    match program {
+        None | Some("spl-token") => Ok(spl_token::id()),
+        Some("token-2022") => Ok(spl_token_2022::id()),
+        Some(other) => Err(format!(
+            "Unknown `program` `{}`, expected `spl-token` or `token-2022`",
+            other
+        )),
+    }
+}
+
+// Parse the signer addresses of an SPL multisig authority. An empty list
+// means the authority signs for itself.
+pub fn parse_signers(signers: &[String]) -> Result<Vec<Pubkey>, String> {
+    if signers.len() > MAX_SIGNERS {
+        return Err(format!("At most {} multisig signers are allowed", MAX_SIGNERS));
+    }
+    let mut parsed = Vec::with_capacity(signers.len());
+    for (i, signer) in signers.iter().enumerate() {
+        let pk = signer
+            .parse::<Pubkey>()
+            .map_err(|_| format!("Invalid `signers` address at index {}", i))?;
+        if parsed.contains(&pk) {
+            return Err(format!("Duplicate `signers` address at index {}", i));
         }
-    })))
-}
\ No newline at end of file
+        parsed.push(pk);
+    }
+    Ok(parsed)
+}
+
+// Parse a base58 address from the request field `field`
+pub fn parse_address(value: &str, field: &str) -> Result<Pubkey, (StatusCode, Json<serde_json::Value>)> {
+    value
+        .parse::<Pubkey>()
+        .map_err(|_| error_response(StatusCode::BAD_REQUEST, format!("Invalid `{}` address", field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_exact_decimal_amounts() {
+        assert_eq!(ui_amount_to_amount("0.000000001", 9), Ok(1));
+        assert_eq!(ui_amount_to_amount("1.5", 6), Ok(1_500_000));
+        assert_eq!(ui_amount_to_amount("42", 0), Ok(42));
+        assert_eq!(ui_amount_to_amount("0.1", 9), Ok(100_000_000));
+        assert_eq!(ui_amount_to_amount("0.3", 18), Ok(300_000_000_000_000_000));
+        // 2^53 + 1 base units, which f64 rounds to 2^53 + 2
+        assert_eq!(ui_amount_to_amount("9007199254.740993", 6), Ok(9_007_199_254_740_993));
+    }
+
+    #[test]
+    fn ignores_trailing_zeros_past_decimals() {
+        assert_eq!(ui_amount_to_amount("1.500000000000", 2), Ok(150));
+        assert_eq!(ui_amount_to_amount("2.0", 0), Ok(2));
+    }
+
+    #[test]
+    fn rejects_too_many_fractional_digits() {
+        assert!(ui_amount_to_amount("0.0000000001", 9).is_err());
+        assert!(ui_amount_to_amount("1.5", 0).is_err());
+    }
+
+    #[test]
+    fn handles_leading_and_trailing_dots() {
+        assert_eq!(ui_amount_to_amount(".5", 1), Ok(5));
+        assert_eq!(ui_amount_to_amount("5.", 1), Ok(50));
+        for invalid in [".", "", "1.2.3", "-1", "+1", "1e3", " 1", "1,5", "0x10"] {
+            assert!(ui_amount_to_amount(invalid, 9).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn handles_u64_bounds_and_max_decimals() {
+        assert_eq!(ui_amount_to_amount("18446744073709551615", 0), Ok(u64::MAX));
+        assert_eq!(ui_amount_to_amount("18.446744073709551615", 18), Ok(u64::MAX));
+        assert!(ui_amount_to_amount("18446744073709551616", 0).is_err());
+        assert!(ui_amount_to_amount("18.446744073709551616", 18).is_err());
+        assert!(ui_amount_to_amount("18446744073709551615", 1).is_err());
+        // Values far beyond u128 still fail cleanly
+        assert!(ui_amount_to_amount(&"9".repeat(60), 9).is_err());
+        assert!(ui_amount_to_amount("1", u8::MAX).is_err());
+        // Zero fits whatever the decimals
+        assert_eq!(ui_amount_to_amount("0", 39), Ok(0));
+        assert_eq!(ui_amount_to_amount("0.000", u8::MAX), Ok(0));
+    }
+
+    #[test]
+    fn matches_token_2022_parsing() {
+        for (ui_amount, decimals) in [("1.23", 2), ("0.000000001", 9), (".5", 3), ("7.", 4), ("1000000", 6)] {
+            assert_eq!(
+                ui_amount_to_amount(ui_amount, decimals).ok(),
+                spl_token_2022::try_ui_amount_into_amount(ui_amount.to_string(), decimals).ok(),
+                "{}",
+                ui_amount
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_amount_or_ui_amount() {
+        assert_eq!(resolve_amount(Some(5), None, None), Ok(5));
+        assert_eq!(resolve_amount(None, Some("0.05"), Some(2)), Ok(5));
+        assert!(resolve_amount(None, Some("0.05"), None).is_err());
+        assert!(resolve_amount(Some(5), Some("0.05"), Some(2)).is_err());
+        assert!(resolve_amount(None, None, Some(2)).is_err());
+    }
+}
@@ -0,0 +1,721 @@
+use axum::{Json, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use solana_program::hash::Hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::address_lookup_table::AddressLookupTableAccount;
+use solana_program::message::{v0, Message, VersionedMessage};
+use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use base64::engine::general_purpose::STANDARD as base64_engine;
+use base64::Engine;
+use bs58::encode as bs58_encode;
+use axum::http::StatusCode;
+use serde_json::json;
+
+use super::decode;
+
+// An instruction in the shape every builder endpoint emits
+#[derive(Deserialize)]
+pub struct InstructionInput {
+    pub program_id: String,
+    pub accounts: Vec<AccountMetaInput>,
+    pub instruction_data: String,
+}
+
+#[derive(Deserialize)]
+pub struct AccountMetaInput {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+// A lookup table as fetched by the client: its address and current address list
+#[derive(Deserialize)]
+pub struct LookupTableInput {
+    pub address: String,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BuildTransactionRequest {
+    pub instructions: Vec<InstructionInput>,
+    #[serde(rename = "feePayer")]
+    pub fee_payer: String,
+    // Required unless `nonce` is given
+    #[serde(rename = "recentBlockhash")]
+    pub recent_blockhash: Option<String>,
+    // "legacy" (default) or "v0"
+    pub version: Option<String>,
+    #[serde(rename = "addressLookupTables", default)]
+    pub address_lookup_tables: Vec<LookupTableInput>,
+    // Use a durable nonce in place of a recent blockhash
+    pub nonce: Option<NonceInput>,
+}
+
+#[derive(Deserialize)]
+pub struct NonceInput {
+    pub account: String,
+    pub authority: String,
+    // The blockhash currently stored in the nonce account
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+pub struct DecodeTransactionRequest {
+    pub transaction: String,
+    // "base64" (default) or "base58"
+    pub encoding: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LookupResolution {
+    pub table: String,
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BuildTransactionResponse {
+    pub version: String,
+    pub transaction_base64: String,
+    pub transaction_base58: String,
+    pub message_base64: String,
+    pub signers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lookups: Vec<LookupResolution>,
+    pub size: usize,
+}
+
+pub async fn build_transaction(Json(payload): Json<BuildTransactionRequest>) -> impl IntoResponse {
+    // Validate required fields
+    if payload.instructions.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": "At least one instruction is required"
+            }))
+        );
+    }
+
+    // Parse fee payer public key
+    let fee_payer = match payload.fee_payer.parse::<Pubkey>() {
+        Ok(pk) => pk,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid `feePayer` address"
+                }))
+            );
+        }
+    };
+
+    // A nonced transaction takes the stored nonce as its blockhash and must
+    // advance the nonce in its first instruction
+    let (blockhash_input, field, advance_nonce) = match (payload.recent_blockhash.as_deref(), payload.nonce.as_ref()) {
+        (Some(recent_blockhash), None) => (recent_blockhash, "recentBlockhash", None),
+        (None, Some(nonce)) => {
+            let account = match nonce.account.parse::<Pubkey>() {
+                Ok(pk) => pk,
+                Err(_) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "success": false,
+                            "error": "Invalid `nonce.account` address"
+                        }))
+                    );
+                }
+            };
+            let authority = match nonce.authority.parse::<Pubkey>() {
+                Ok(pk) => pk,
+                Err(_) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "success": false,
+                            "error": "Invalid `nonce.authority` address"
+                        }))
+                    );
+                }
+            };
+            (
+                nonce.value.as_str(),
+                "nonce.value",
+                Some(system_instruction::advance_nonce_account(&account, &authority)),
+            )
+        }
+        (Some(_), Some(_)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Provide either `recentBlockhash` or `nonce`, not both"
+                }))
+            );
+        }
+        (None, None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Missing required field `recentBlockhash` or `nonce`"
+                }))
+            );
+        }
+    };
+
+    // Parse recent blockhash
+    let blockhash = match blockhash_input.parse::<Hash>() {
+        Ok(hash) => hash,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Invalid `{}`", field)
+                }))
+            );
+        }
+    };
+
+    // Rebuild each instruction from its JSON form
+    let mut instructions = Vec::with_capacity(payload.instructions.len() + 1);
+    for (i, input) in payload.instructions.iter().enumerate() {
+        match parse_instruction(input) {
+            Ok(ix) => instructions.push(ix),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Invalid instruction {}: {}", i, e)
+                    }))
+                );
+            }
+        }
+    }
+
+    // Skip the insert when the caller already put the advance first
+    if let Some(advance_nonce) = advance_nonce {
+        if instructions.first() != Some(&advance_nonce) {
+            instructions.insert(0, advance_nonce);
+        }
+    }
+
+    // Parse lookup tables, only meaningful for v0 messages
+    let mut lookup_tables = Vec::with_capacity(payload.address_lookup_tables.len());
+    for (i, table) in payload.address_lookup_tables.iter().enumerate() {
+        match parse_lookup_table(table) {
+            Ok(table) => lookup_tables.push(table),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Invalid address lookup table {}: {}", i, e)
+                    }))
+                );
+            }
+        }
+    }
+
+    let message = match payload.version.as_deref().unwrap_or("legacy") {
+        "legacy" => {
+            if !lookup_tables.is_empty() {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": "Address lookup tables require a `v0` transaction"
+                    }))
+                );
+            }
+            // `Message::new_with_blockhash` panics past 256 unique accounts or 255 of a
+            // kind, so compile the same keys fallibly first to reject those with a 400
+            if let Err(e) = v0::Message::try_compile(&fee_payer, &instructions, &[], blockhash) {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Failed to compile legacy message: {}", e)
+                    }))
+                );
+            }
+            VersionedMessage::Legacy(Message::new_with_blockhash(
+                &instructions,
+                Some(&fee_payer),
+                &blockhash,
+            ))
+        }
+        "v0" | "0" => match v0::Message::try_compile(&fee_payer, &instructions, &lookup_tables, blockhash) {
+            Ok(message) => VersionedMessage::V0(message),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "success": false,
+                        "error": format!("Failed to compile v0 message: {}", e)
+                    }))
+                );
+            }
+        },
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Unsupported transaction version `{}`", other)
+                }))
+            );
+        }
+    };
+
+    let (tx_bytes, response) = match encode_unsigned_transaction(message, &lookup_tables) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Failed to serialize transaction: {}", e)
+                }))
+            );
+        }
+    };
+
+    // Anything larger than a packet cannot be submitted to the cluster
+    if tx_bytes.len() > PACKET_DATA_SIZE {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": format!(
+                    "Transaction is {} bytes, exceeding the {} byte packet limit",
+                    tx_bytes.len(),
+                    PACKET_DATA_SIZE
+                )
+            }))
+        );
+    }
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": response
+    })))
+}
+
+// Wrap a compiled message in a transaction with empty signature slots and
+// describe it, returning the wire bytes alongside the response body
+pub fn encode_unsigned_transaction(
+    message: VersionedMessage,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<(Vec<u8>, BuildTransactionResponse), bincode::Error> {
+    let num_signers = message.header().num_required_signatures as usize;
+    let signers = message.static_account_keys()[..num_signers]
+        .iter()
+        .map(|pk| pk.to_string())
+        .collect();
+    let version = match message {
+        VersionedMessage::Legacy(_) => "legacy",
+        VersionedMessage::V0(_) => "v0",
+    };
+
+    // Report which table slots the compiler picked for each lookup
+    let lookups = message
+        .address_table_lookups()
+        .unwrap_or_default()
+        .iter()
+        .map(|lookup| {
+            let addresses = lookup_tables
+                .iter()
+                .find(|table| table.key == lookup.account_key)
+                .map(|table| table.addresses.as_slice())
+                .unwrap_or_default();
+            let resolve = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .filter_map(|i| addresses.get(*i as usize))
+                    .map(|pk| pk.to_string())
+                    .collect()
+            };
+            LookupResolution {
+                table: lookup.account_key.to_string(),
+                writable: resolve(&lookup.writable_indexes),
+                readonly: resolve(&lookup.readonly_indexes),
+            }
+        })
+        .collect();
+
+    let message_bytes = message.serialize();
+    let tx = VersionedTransaction {
+        signatures: vec![Signature::default(); num_signers],
+        message,
+    };
+    let tx_bytes = bincode::serialize(&tx)?;
+
+    let response = BuildTransactionResponse {
+        version: version.to_string(),
+        transaction_base64: base64_engine.encode(&tx_bytes),
+        transaction_base58: bs58_encode(&tx_bytes).into_string(),
+        message_base64: base64_engine.encode(&message_bytes),
+        signers,
+        lookups,
+        size: tx_bytes.len(),
+    };
+    Ok((tx_bytes, response))
+}
+
+fn parse_lookup_table(input: &LookupTableInput) -> Result<AddressLookupTableAccount, String> {
+    let key = input
+        .address
+        .parse::<Pubkey>()
+        .map_err(|_| "invalid table `address`".to_string())?;
+    let addresses = input
+        .addresses
+        .iter()
+        .enumerate()
+        .map(|(i, address)| {
+            address
+                .parse::<Pubkey>()
+                .map_err(|_| format!("invalid address at index {}", i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(AddressLookupTableAccount { key, addresses })
+}
+
+pub fn parse_instruction(input: &InstructionInput) -> Result<Instruction, String> {
+    let program_id = input
+        .program_id
+        .parse::<Pubkey>()
+        .map_err(|_| "invalid `program_id` address".to_string())?;
+
+    let mut accounts = Vec::with_capacity(input.accounts.len());
+    for (i, acct) in input.accounts.iter().enumerate() {
+        let pubkey = acct
+            .pubkey
+            .parse::<Pubkey>()
+            .map_err(|_| format!("invalid address for account {}", i))?;
+        accounts.push(AccountMeta {
+            pubkey,
+            is_signer: acct.is_signer,
+            is_writable: acct.is_writable,
+        });
+    }
+
+    let data = base64_engine
+        .decode(&input.instruction_data)
+        .map_err(|_| "invalid base64 encoding for `instruction_data`".to_string())?;
+
+    Ok(Instruction { program_id, accounts, data })
+}
+
+pub async fn decode_transaction(Json(payload): Json<DecodeTransactionRequest>) -> impl IntoResponse {
+    // Validate required fields
+    if payload.transaction.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": "Missing required fields"
+            }))
+        );
+    }
+
+    // Decode the wire bytes
+    let tx_bytes = match payload.encoding.as_deref().unwrap_or("base64") {
+        "base64" => base64_engine.decode(&payload.transaction).ok(),
+        "base58" => bs58::decode(&payload.transaction).into_vec().ok(),
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Unsupported encoding `{}`", other)
+                }))
+            );
+        }
+    };
+    let tx_bytes = match tx_bytes {
+        Some(bytes) => bytes,
+        None => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid encoding for transaction"
+                }))
+            );
+        }
+    };
+
+    let tx = match bincode::deserialize::<VersionedTransaction>(&tx_bytes) {
+        Ok(tx) => tx,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": format!("Invalid transaction: {}", e)
+                }))
+            );
+        }
+    };
+
+    if let Err(e) = tx.sanitize() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "error": format!("Invalid transaction: {}", e)
+            }))
+        );
+    }
+
+    let message = &tx.message;
+    let header = message.header();
+    let static_keys = message.static_account_keys();
+    let lookups = message.address_table_lookups().unwrap_or_default();
+
+    let account_keys = static_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| json!({
+            "pubkey": key.to_string(),
+            "is_signer": message.is_signer(i),
+            "is_writable": message.is_maybe_writable(i),
+        }))
+        .collect::<Vec<_>>();
+
+    // Accounts loaded through lookup tables follow the static keys, all
+    // writable loads first, then all readonly loads. Their addresses are only
+    // known on-chain, so they are labelled by table and index.
+    let mut keys = static_keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+    for lookup in lookups {
+        keys.extend(lookup.writable_indexes.iter().map(|i| format!("{}[{}]", lookup.account_key, i)));
+    }
+    for lookup in lookups {
+        keys.extend(lookup.readonly_indexes.iter().map(|i| format!("{}[{}]", lookup.account_key, i)));
+    }
+
+    // Check each signature against the serialized message
+    let message_bytes = message.serialize();
+    let signatures = tx
+        .signatures
+        .iter()
+        .zip(static_keys)
+        .map(|(sig, key)| {
+            let status = if *sig == Signature::default() {
+                "missing"
+            } else if sig.verify(key.as_ref(), &message_bytes) {
+                "valid"
+            } else {
+                "invalid"
+            };
+            json!({
+                "signer": key.to_string(),
+                "signature": sig.to_string(),
+                "status": status,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|ix| {
+            let program_id = static_keys[ix.program_id_index as usize];
+            let accounts = ix
+                .accounts
+                .iter()
+                .map(|i| keys[*i as usize].clone())
+                .collect::<Vec<_>>();
+            json!({
+                "program_id": program_id.to_string(),
+                "program": decode::program_name(&program_id),
+                "parsed": decode::parse_instruction(&program_id, &accounts, &ix.data),
+                "accounts": accounts,
+                "instruction_data": base64_engine.encode(&ix.data),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let version = match message {
+        VersionedMessage::Legacy(_) => "legacy",
+        VersionedMessage::V0(_) => "v0",
+    };
+
+    (StatusCode::OK, Json(json!({
+        "success": true,
+        "data": {
+            "version": version,
+            "header": {
+                "num_required_signatures": header.num_required_signatures,
+                "num_readonly_signed_accounts": header.num_readonly_signed_accounts,
+                "num_readonly_unsigned_accounts": header.num_readonly_unsigned_accounts,
+            },
+            "account_keys": account_keys,
+            "address_table_lookups": lookups.iter().map(|lookup| json!({
+                "table": lookup.account_key.to_string(),
+                "writable_indexes": lookup.writable_indexes,
+                "readonly_indexes": lookup.readonly_indexes,
+            })).collect::<Vec<_>>(),
+            "recent_blockhash": message.recent_blockhash().to_string(),
+            "signatures": signatures,
+            "instructions": instructions,
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use spl_associated_token_account::get_associated_token_address;
+    use super::super::response::response_json;
+    use super::super::transfer::{send_token, SendTokenRequest};
+
+    fn request(accounts: usize, signers: bool) -> BuildTransactionRequest {
+        let accounts = (0..accounts)
+            .map(|_| json!({
+                "pubkey": Pubkey::new_unique().to_string(),
+                "is_signer": signers,
+                "is_writable": true,
+            }))
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({
+            "instructions": [{
+                "program_id": Pubkey::new_unique().to_string(),
+                "accounts": accounts,
+                "instruction_data": "",
+            }],
+            "feePayer": Pubkey::new_unique().to_string(),
+            "recentBlockhash": Hash::new_unique().to_string(),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejects_legacy_message_with_too_many_accounts() {
+        let response = build_transaction(Json(request(300, false))).await.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = build_transaction(Json(request(255, true))).await.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn builds_legacy_message_within_limits() {
+        let response = build_transaction(Json(request(4, false))).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // Compile builder instructions into a transaction and decode it again
+    async fn build_and_decode(instructions: &Value, fee_payer: &Pubkey, version: &str, tables: Value) -> Value {
+        let request = serde_json::from_value(json!({
+            "instructions": instructions,
+            "feePayer": fee_payer.to_string(),
+            "recentBlockhash": Hash::new_unique().to_string(),
+            "version": version,
+            "addressLookupTables": tables,
+        }))
+        .unwrap();
+        let (status, built) = response_json(build_transaction(Json(request)).await).await;
+        assert_eq!(status, StatusCode::OK, "{}", built);
+
+        let request = DecodeTransactionRequest {
+            transaction: built["data"]["transaction_base64"].as_str().unwrap().to_string(),
+            encoding: None,
+        };
+        let (status, decoded) = response_json(decode_transaction(Json(request)).await).await;
+        assert_eq!(status, StatusCode::OK, "{}", decoded);
+        decoded["data"].clone()
+    }
+
+    fn parsed_types(decoded: &Value) -> Vec<&str> {
+        decoded["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ix| ix["parsed"]["type"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn decodes_send_token_output() {
+        let (owner, wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let request: SendTokenRequest = serde_json::from_value(json!({
+            "destination": wallet.to_string(),
+            "mint": mint.to_string(),
+            "owner": owner.to_string(),
+            "amount": 5,
+            "decimals": 6,
+            "createDestinationAccount": true,
+            "memo": "invoice 42",
+            "computeUnitLimit": 60_000,
+            "microLamportsPerCu": 10,
+        }))
+        .unwrap();
+        let (status, sent) = response_json(send_token(Json(request)).await).await;
+        assert_eq!(status, StatusCode::OK);
+        let source = get_associated_token_address(&owner, &mint).to_string();
+        let destination = get_associated_token_address(&wallet, &mint).to_string();
+
+        let decoded = build_and_decode(&sent["data"]["instructions"], &owner, "legacy", json!([])).await;
+        assert_eq!(decoded["version"], "legacy");
+        assert_eq!(
+            parsed_types(&decoded),
+            ["setComputeUnitLimit", "setComputeUnitPrice", "createIdempotent", "memo", "transferChecked"]
+        );
+        let parsed = |i: usize| decoded["instructions"][i]["parsed"]["info"].clone();
+        assert_eq!(parsed(0)["units"], 60_000);
+        assert_eq!(parsed(1)["microLamports"], "10");
+        assert_eq!(parsed(2)["source"], owner.to_string());
+        assert_eq!(parsed(2)["account"], destination);
+        assert_eq!(parsed(2)["wallet"], wallet.to_string());
+        assert_eq!(parsed(3), json!({ "memo": "invoice 42", "signers": [owner.to_string()] }));
+        assert_eq!(parsed(4)["source"], source);
+        assert_eq!(parsed(4)["mint"], mint.to_string());
+        assert_eq!(parsed(4)["destination"], destination);
+        assert_eq!(parsed(4)["authority"], owner.to_string());
+        assert_eq!(parsed(4)["amount"], "5");
+        assert_eq!(decoded["account_keys"][0]["pubkey"], owner.to_string());
+        assert_eq!(decoded["account_keys"][0]["is_signer"], true);
+    }
+
+    #[tokio::test]
+    async fn labels_lookup_table_accounts_by_table_and_index() {
+        let (owner, wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let request: SendTokenRequest = serde_json::from_value(json!({
+            "destination": wallet.to_string(),
+            "mint": mint.to_string(),
+            "owner": owner.to_string(),
+            "amount": 5,
+            "decimals": 6,
+            "createDestinationAccount": true,
+        }))
+        .unwrap();
+        let (_, sent) = response_json(send_token(Json(request)).await).await;
+        let destination = get_associated_token_address(&wallet, &mint).to_string();
+
+        // The mint is only read and the destination written, so they load from separate index lists
+        let table = Pubkey::new_unique();
+        let tables = json!([{ "address": table.to_string(), "addresses": [mint.to_string(), destination] }]);
+        let decoded = build_and_decode(&sent["data"]["instructions"], &owner, "v0", tables).await;
+        assert_eq!(decoded["version"], "v0");
+        assert_eq!(decoded["address_table_lookups"][0]["writable_indexes"], json!([1]));
+        assert_eq!(decoded["address_table_lookups"][0]["readonly_indexes"], json!([0]));
+        assert_eq!(parsed_types(&decoded), ["createIdempotent", "transferChecked"]);
+
+        let transfer = &decoded["instructions"][1]["parsed"]["info"];
+        assert_eq!(transfer["mint"], format!("{}[0]", table));
+        assert_eq!(transfer["destination"], format!("{}[1]", table));
+        assert_eq!(transfer["authority"], owner.to_string());
+        assert_eq!(decoded["instructions"][0]["parsed"]["info"]["account"], format!("{}[1]", table));
+    }
+}
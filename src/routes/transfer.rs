@@ -1,20 +1,49 @@
 use axum::{Json, response::IntoResponse};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use solana_program::hash::Hash;
 use solana_program::instruction::Instruction;
+use solana_program::message::{Message, VersionedMessage};
 use solana_program::pubkey::Pubkey;
-use solana_program::system_instruction;
-use spl_token::instruction as token_instruction;
-use base64::engine::general_purpose::STANDARD as base64_engine;
-use base64::Engine;
-use solana_sdk::signature::read_keypair_file;
+use solana_program::system_instruction::{self, MAX_PERMITTED_DATA_LENGTH};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use spl_token_2022::instruction as token_instruction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 use axum::http::StatusCode;
 use serde_json::json;
 
+use super::compute_budget::{compute_budget_instructions, MAX_COMPUTE_UNIT_LIMIT};
+use super::memo::memo_instruction;
+use super::rent::{rent_exempt_minimum, RentInput};
+use super::response::{error_response, instruction_response, InstructionResponse};
+use super::token::{parse_address, parse_signers, resolve_amount, token_program_id, AuthorityOptions};
+use super::transaction::{encode_unsigned_transaction, BuildTransactionResponse};
+
+const SOL_DECIMALS: u8 = 9;
+
+// Default compute estimates per recipient when packing batch payouts, roughly
+// twice the units observed for plain mints. They cannot account for Token-2022
+// extensions: a transfer hook runs arbitrary code and ATA creation reallocates
+// for each required extension, so callers paying out such mints should pass
+// `computeUnitsPerRecipient` instead.
+const SOL_TRANSFER_COMPUTE_UNITS: u32 = 450;
+const TOKEN_TRANSFER_COMPUTE_UNITS: u32 = 12_000;
+const CREATE_ATA_COMPUTE_UNITS: u32 = 35_000;
+// Token-2022 does more work than spl-token even without extensions
+const TOKEN_2022_TRANSFER_COMPUTE_UNITS: u32 = 20_000;
+const TOKEN_2022_CREATE_ATA_COMPUTE_UNITS: u32 = 60_000;
+
 #[derive(Deserialize)]
 pub struct SendSolRequest {
     pub from: String,
     pub to: String,
     pub lamports: u64,
+    // Attached as an SPL Memo signed by `from`
+    pub memo: Option<String>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -22,181 +51,818 @@ pub struct SendTokenRequest {
     pub destination: String,
     pub mint: String,
     pub owner: String,
-    pub amount: u64,
+    // Base units; alternatively `uiAmount` with `decimals`
+    pub amount: Option<u64>,
+    #[serde(rename = "uiAmount")]
+    pub ui_amount: Option<String>,
+    // When set, emits `transfer_checked` against the mint's decimals
+    pub decimals: Option<u8>,
+    // Prepend an idempotent create of the recipient's associated token account
+    #[serde(rename = "createDestinationAccount", default)]
+    pub create_destination_account: bool,
+    // Funds the associated token account creation, defaults to `owner`
+    pub payer: Option<String>,
+    // Attached as an SPL Memo signed by `owner`, or by `signers` for a multisig owner
+    pub memo: Option<String>,
+    #[serde(rename = "computeUnitLimit", alias = "compute_unit_limit")]
+    pub compute_unit_limit: Option<u32>,
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    #[serde(flatten)]
+    pub options: AuthorityOptions,
 }
 
-#[derive(Serialize)]
-pub struct TransferResponse {
-    pub program_id: String,
-    pub accounts: Vec<String>,
-    pub instruction_data: String,
+#[derive(Deserialize)]
+pub struct CreateAccountRequest {
+    pub from: String,
+    #[serde(rename = "newAccount")]
+    pub new_account: String,
+    // Data length in bytes
+    pub space: u64,
+    // Program that will own the account
+    pub owner: String,
+    // Defaults to the rent exempt minimum for `space`
+    pub lamports: Option<u64>,
+    pub rent: Option<RentInput>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateAccountWithSeedRequest {
+    pub from: String,
+    pub base: String,
+    pub seed: String,
+    pub space: u64,
+    pub owner: String,
+    pub lamports: Option<u64>,
+    pub rent: Option<RentInput>,
+}
+
+#[derive(Deserialize)]
+pub struct AllocateRequest {
+    pub account: String,
+    pub space: u64,
+}
+
+#[derive(Deserialize)]
+pub struct AssignRequest {
+    pub account: String,
+    pub owner: String,
+}
+
+#[derive(Deserialize)]
+pub struct TransferWithSeedRequest {
+    // The source is the address derived from `base`, `seed` and `fromOwner`
+    pub base: String,
+    pub seed: String,
+    #[serde(rename = "fromOwner")]
+    pub from_owner: String,
+    pub to: String,
+    pub lamports: u64,
+}
+
+#[derive(Deserialize)]
+pub struct TransferManyRequest {
+    pub from: String,
+    pub transfers: Vec<TransferInput>,
+}
+
+#[derive(Deserialize)]
+pub struct TransferInput {
+    pub to: String,
+    pub lamports: u64,
+}
+
+#[derive(Deserialize)]
+pub struct BatchSendRequest {
+    // Wallet paying out SOL, or owning the source token account
+    pub from: String,
+    // Defaults to `from`, also funds associated token account creation
+    #[serde(rename = "feePayer")]
+    pub fee_payer: Option<String>,
+    #[serde(rename = "recentBlockhash")]
+    pub recent_blockhash: String,
+    // Pays out this SPL token instead of SOL
+    pub mint: Option<String>,
+    // Required for `uiAmount` and for Token-2022, emits `transfer_checked`
+    pub decimals: Option<u8>,
+    pub program: Option<String>,
+    // Prepend an idempotent create of each recipient's associated token account
+    #[serde(rename = "createDestinationAccounts", default = "default_create_destination_accounts")]
+    pub create_destination_accounts: bool,
+    pub recipients: Vec<RecipientInput>,
+    // Adds a compute unit limit and price to every transaction
+    #[serde(rename = "microLamportsPerCu", alias = "micro_lamports_per_cu")]
+    pub micro_lamports_per_cu: Option<u64>,
+    // Compute units budgeted for each recipient, replacing the default estimates
+    #[serde(rename = "computeUnitsPerRecipient")]
+    pub compute_units_per_recipient: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct RecipientInput {
+    // A wallet; token payouts go to its associated token account
+    pub address: String,
+    // Lamports or token base units; alternatively `uiAmount`
+    pub amount: Option<u64>,
+    #[serde(rename = "uiAmount")]
+    pub ui_amount: Option<String>,
+}
+
+fn default_create_destination_accounts() -> bool {
+    true
 }
 
 pub async fn send_sol(Json(payload): Json<SendSolRequest>) -> impl IntoResponse {
     // Validate required fields
     if payload.from.is_empty() || payload.to.is_empty() {
-        return (
+        return error_response(
             StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Missing required fields: from and to addresses are required"
-            }))
+            "Missing required fields: from and to addresses are required".to_string(),
         );
     }
 
     // Validate lamports amount
     if payload.lamports == 0 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Amount must be greater than 0"
-            }))
-        );
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
     }
 
     // Parse 'from' public key
-    let from = match payload.from.parse::<Pubkey>() {
+    let from = match parse_address(&payload.from, "from") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `from` address"
-                }))
-            );
-        }
+        Err(response) => return response,
     };
 
     // Parse 'to' public key
-    let to = match payload.to.parse::<Pubkey>() {
+    let to = match parse_address(&payload.to, "to") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `to` address"
-                }))
-            );
-        }
+        Err(response) => return response,
     };
 
     // Create transfer instruction
     let ix: Instruction = system_instruction::transfer(&from, &to, payload.lamports);
-    let data = base64_engine.encode(&ix.data);
-    let accounts = ix.accounts.iter().map(|acct| acct.pubkey.to_string()).collect();
 
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": TransferResponse {
-            program_id: ix.program_id.to_string(),
-            accounts,
-            instruction_data: data,
+    // Optional compute budget instructions run ahead of the main instruction
+    let instruction_count = 1 + payload.memo.is_some() as u32;
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        instruction_count,
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    // The memo goes directly before the transfer, where memo-required accounts expect it
+    if let Some(memo) = payload.memo.as_deref() {
+        match memo_instruction(memo, &[from]) {
+            Ok(memo_ix) => instructions.push(memo_ix),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
         }
-    })))
+    }
+    instructions.push(ix);
+
+    InstructionResponse::new(&instructions).priority_fee(priority_fee).respond()
 }
 
 pub async fn send_token(Json(payload): Json<SendTokenRequest>) -> impl IntoResponse {
     // Validate required fields
     if payload.destination.is_empty() || payload.mint.is_empty() || payload.owner.is_empty() {
-        return (
+        return error_response(
             StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Missing required fields: destination, mint, and owner addresses are required"
-            }))
+            "Missing required fields: destination, mint, and owner addresses are required".to_string(),
         );
     }
 
-    // Validate amount
-    if payload.amount == 0 {
-        return (
+    // Select the token program
+    let token_program = match token_program_id(payload.options.program.as_deref()) {
+        Ok(program) => program,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    // Token-2022 rejects unchecked transfers for mints with fee or hook extensions
+    if token_program == spl_token_2022::id() && payload.decimals.is_none() {
+        return error_response(
             StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Amount must be greater than 0"
-            }))
+            "Token-2022 transfers require `decimals`".to_string(),
         );
     }
 
+    // Resolve the amount in base units
+    let amount = match resolve_amount(payload.amount, payload.ui_amount.as_deref(), payload.decimals) {
+        Ok(amount) => amount,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+
+    // Validate amount
+    if amount == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+
     // Parse destination public key
-    let dest = match payload.destination.parse::<Pubkey>() {
+    let dest = match parse_address(&payload.destination, "destination") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `destination` address"
-                }))
+        Err(response) => return response,
+    };
+
+    // Parse mint public key
+    let mint = match parse_address(&payload.mint, "mint") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    // Parse owner public key
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    // Parse optional payer public key
+    let payer = match payload.payer.as_deref() {
+        None => owner,
+        Some(payer) => match parse_address(payer, "payer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+
+    // Parse multisig signer public keys
+    let signers = match parse_signers(&payload.options.signers) {
+        Ok(signers) => signers,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    let signer_refs = signers.iter().collect::<Vec<_>>();
+
+    // `owner` and `destination` are wallets, tokens move between their associated token accounts
+    let source_ata = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+    let dest_ata = get_associated_token_address_with_program_id(&dest, &mint, &token_program);
+
+    // Create token transfer instruction, checked against the mint decimals when given.
+    // Unchecked transfers only reach here for the original token program.
+    let ix = match payload.decimals {
+        None => spl_token::instruction::transfer(
+            &token_program,
+            &source_ata,
+            &dest_ata,
+            &owner,
+            &signer_refs,
+            amount,
+        ),
+        Some(decimals) => token_instruction::transfer_checked(
+            &token_program,
+            &source_ata,
+            &mint,
+            &dest_ata,
+            &owner,
+            &signer_refs,
+            amount,
+            decimals,
+        ),
+    };
+    let ix = match ix {
+        Ok(instruction) => instruction,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to create token transfer instruction: {}", e),
             );
         }
     };
 
-    // Parse mint public key
-    let mint = match payload.mint.parse::<Pubkey>() {
+    // Optional compute budget instructions run ahead of everything else
+    let instruction_count =
+        1 + payload.create_destination_account as u32 + payload.memo.is_some() as u32;
+    let (mut instructions, priority_fee) = match compute_budget_instructions(
+        payload.compute_unit_limit,
+        payload.micro_lamports_per_cu,
+        instruction_count,
+    ) {
+        Ok(budget) => budget,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+    };
+    if payload.create_destination_account {
+        instructions.push(create_associated_token_account_idempotent(
+            &payer,
+            &dest,
+            &mint,
+            &token_program,
+        ));
+    }
+    // The memo goes directly before the transfer, where memo-required accounts expect it
+    if let Some(memo) = payload.memo.as_deref() {
+        let owner_signer = [owner];
+        let memo_signers = if signers.is_empty() { &owner_signer[..] } else { &signers[..] };
+        match memo_instruction(memo, memo_signers) {
+            Ok(memo_ix) => instructions.push(memo_ix),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        }
+    }
+    instructions.push(ix);
+
+    InstructionResponse::new(&instructions)
+        .priority_fee(priority_fee)
+        .field("source_token_account", source_ata.to_string())
+        .field("destination_token_account", dest_ata.to_string())
+        .respond()
+}
+
+pub async fn create_account(Json(payload): Json<CreateAccountRequest>) -> impl IntoResponse {
+    let from = match parse_address(&payload.from, "from") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let new_account = match parse_address(&payload.new_account, "newAccount") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
+        Err(response) => return response,
+    };
+    let (lamports, rent_exempt_lamports) =
+        match account_lamports(payload.space, payload.lamports, payload.rent.as_ref()) {
+            Ok(lamports) => lamports,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        };
+
+    let ix = system_instruction::create_account(&from, &new_account, lamports, payload.space, &owner);
+    account_response(ix, &new_account, rent_exempt_lamports, payload.space)
+}
+
+pub async fn create_account_with_seed(Json(payload): Json<CreateAccountWithSeedRequest>) -> impl IntoResponse {
+    let from = match parse_address(&payload.from, "from") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let base = match parse_address(&payload.base, "base") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let address = match Pubkey::create_with_seed(&base, &payload.seed, &owner) {
+        Ok(address) => address,
+        Err(e) => {
+            return error_response(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `mint` address"
-                }))
+                format!("Failed to derive address with seed: {}", e),
             );
         }
     };
+    let (lamports, rent_exempt_lamports) =
+        match account_lamports(payload.space, payload.lamports, payload.rent.as_ref()) {
+            Ok(lamports) => lamports,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        };
 
-    // Parse owner public key
-    let owner = match payload.owner.parse::<Pubkey>() {
+    let ix = system_instruction::create_account_with_seed(
+        &from,
+        &address,
+        &base,
+        &payload.seed,
+        lamports,
+        payload.space,
+        &owner,
+    );
+    account_response(ix, &address, rent_exempt_lamports, payload.space)
+}
+
+pub async fn allocate(Json(payload): Json<AllocateRequest>) -> impl IntoResponse {
+    let account = match parse_address(&payload.account, "account") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if payload.space > MAX_PERMITTED_DATA_LENGTH {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("`space` must be at most {} bytes", MAX_PERMITTED_DATA_LENGTH),
+        );
+    }
+
+    instruction_response(Ok(system_instruction::allocate(&account, payload.space)), "allocate")
+}
+
+pub async fn assign(Json(payload): Json<AssignRequest>) -> impl IntoResponse {
+    let account = match parse_address(&payload.account, "account") {
         Ok(pk) => pk,
-        Err(_) => {
-            return (
+        Err(response) => return response,
+    };
+    let owner = match parse_address(&payload.owner, "owner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+
+    instruction_response(Ok(system_instruction::assign(&account, &owner)), "assign")
+}
+
+pub async fn transfer_with_seed(Json(payload): Json<TransferWithSeedRequest>) -> impl IntoResponse {
+    let base = match parse_address(&payload.base, "base") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let from_owner = match parse_address(&payload.from_owner, "fromOwner") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let to = match parse_address(&payload.to, "to") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if payload.lamports == 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be greater than 0".to_string());
+    }
+    let from = match Pubkey::create_with_seed(&base, &payload.seed, &from_owner) {
+        Ok(address) => address,
+        Err(e) => {
+            return error_response(
                 StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid `owner` address"
-                }))
+                format!("Failed to derive address with seed: {}", e),
             );
         }
     };
 
-    // Create token transfer instruction
-    let ix = match token_instruction::transfer(
-        &spl_token::id(), 
-        &mint, 
-        &dest, 
-        &owner, 
-        &[], 
-        payload.amount
-    ) {
-        Ok(instruction) => instruction,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "success": false,
-                    "error": format!("Failed to create token transfer instruction: {}", e)
-                }))
+    instruction_response(
+        Ok(system_instruction::transfer_with_seed(
+            &from,
+            &base,
+            payload.seed.clone(),
+            &from_owner,
+            &to,
+            payload.lamports,
+        )),
+        "transfer_with_seed",
+    )
+}
+
+pub async fn transfer_many(Json(payload): Json<TransferManyRequest>) -> impl IntoResponse {
+    let from = match parse_address(&payload.from, "from") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    if payload.transfers.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "At least one transfer is required".to_string());
+    }
+
+    let mut to_lamports = Vec::with_capacity(payload.transfers.len());
+    let mut total: u64 = 0;
+    for (i, transfer) in payload.transfers.iter().enumerate() {
+        let to = match transfer.to.parse::<Pubkey>() {
+            Ok(pk) => pk,
+            Err(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid `to` address in transfer {}", i),
+                );
+            }
+        };
+        if transfer.lamports == 0 {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Amount must be greater than 0 in transfer {}", i),
             );
         }
+        total = match total.checked_add(transfer.lamports) {
+            Some(total) => total,
+            None => return error_response(StatusCode::BAD_REQUEST, "Total lamports overflow u64".to_string()),
+        };
+        to_lamports.push((to, transfer.lamports));
+    }
+
+    let instructions = system_instruction::transfer_many(&from, &to_lamports);
+    InstructionResponse::new(&instructions).field("total_lamports", total).respond()
+}
+
+pub async fn send_batch(Json(payload): Json<BatchSendRequest>) -> impl IntoResponse {
+    let from = match parse_address(&payload.from, "from") {
+        Ok(pk) => pk,
+        Err(response) => return response,
+    };
+    let fee_payer = match payload.fee_payer.as_deref() {
+        None => from,
+        Some(fee_payer) => match parse_address(fee_payer, "feePayer") {
+            Ok(pk) => pk,
+            Err(response) => return response,
+        },
+    };
+    let blockhash = match payload.recent_blockhash.parse::<Hash>() {
+        Ok(hash) => hash,
+        Err(_) => return error_response(StatusCode::BAD_REQUEST, "Invalid `recentBlockhash`".to_string()),
+    };
+    if payload.recipients.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "At least one recipient is required".to_string());
+    }
+    if let Some(units) = payload.compute_units_per_recipient {
+        if units == 0 || units > MAX_COMPUTE_UNIT_LIMIT {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("`computeUnitsPerRecipient` must be between 1 and {}", MAX_COMPUTE_UNIT_LIMIT),
+            );
+        }
+    }
+
+    // Token payouts move between associated token accounts of the selected program
+    let token = match payload.mint.as_deref() {
+        None => None,
+        Some(mint) => {
+            let mint = match parse_address(mint, "mint") {
+                Ok(pk) => pk,
+                Err(response) => return response,
+            };
+            let token_program = match token_program_id(payload.program.as_deref()) {
+                Ok(program) => program,
+                Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+            };
+            if token_program == spl_token_2022::id() && payload.decimals.is_none() {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    "Token-2022 transfers require `decimals`".to_string(),
+                );
+            }
+            let source = get_associated_token_address_with_program_id(&from, &mint, &token_program);
+            Some((mint, token_program, source))
+        }
     };
 
-    let data = base64_engine.encode(&ix.data);
-    let accounts = ix.accounts.iter().map(|acct| json!({
-        "pubkey": acct.pubkey.to_string(),
-        "is_signer": acct.is_signer,
-        "is_writable": acct.is_writable
-    })).collect::<Vec<_>>();
+    // Build each recipient's instructions with an estimate of their compute cost
+    let mut groups = Vec::with_capacity(payload.recipients.len());
+    let mut manifest = Vec::with_capacity(payload.recipients.len());
+    let mut total: u64 = 0;
+    for (i, recipient) in payload.recipients.iter().enumerate() {
+        let wallet = match recipient.address.parse::<Pubkey>() {
+            Ok(pk) => pk,
+            Err(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid `address` for recipient {}", i),
+                );
+            }
+        };
+        // SOL amounts given as `uiAmount` are in whole SOL
+        let decimals = match token {
+            None => Some(SOL_DECIMALS),
+            Some(_) => payload.decimals,
+        };
+        let amount = match resolve_amount(recipient.amount, recipient.ui_amount.as_deref(), decimals) {
+            Ok(amount) if amount > 0 => amount,
+            Ok(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Amount must be greater than 0 for recipient {}", i),
+                );
+            }
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, format!("Recipient {}: {}", i, e)),
+        };
+        total = match total.checked_add(amount) {
+            Some(total) => total,
+            None => return error_response(StatusCode::BAD_REQUEST, "Total amount overflows u64".to_string()),
+        };
+
+        let (instructions, units, token_account) = match token {
+            None => (
+                vec![system_instruction::transfer(&from, &wallet, amount)],
+                SOL_TRANSFER_COMPUTE_UNITS,
+                None,
+            ),
+            Some((mint, token_program, source)) => {
+                let destination = get_associated_token_address_with_program_id(&wallet, &mint, &token_program);
+                let mut instructions = Vec::with_capacity(2);
+                let (transfer_units, create_units) = if token_program == spl_token_2022::id() {
+                    (TOKEN_2022_TRANSFER_COMPUTE_UNITS, TOKEN_2022_CREATE_ATA_COMPUTE_UNITS)
+                } else {
+                    (TOKEN_TRANSFER_COMPUTE_UNITS, CREATE_ATA_COMPUTE_UNITS)
+                };
+                let mut units = transfer_units;
+                if payload.create_destination_accounts {
+                    instructions.push(create_associated_token_account_idempotent(
+                        &fee_payer,
+                        &wallet,
+                        &mint,
+                        &token_program,
+                    ));
+                    units += create_units;
+                }
+                let ix = match payload.decimals {
+                    None => spl_token::instruction::transfer(&token_program, &source, &destination, &from, &[], amount),
+                    Some(decimals) => token_instruction::transfer_checked(
+                        &token_program,
+                        &source,
+                        &mint,
+                        &destination,
+                        &from,
+                        &[],
+                        amount,
+                        decimals,
+                    ),
+                };
+                match ix {
+                    Ok(ix) => instructions.push(ix),
+                    Err(e) => {
+                        return error_response(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            format!("Failed to create token transfer instruction: {}", e),
+                        );
+                    }
+                }
+                (instructions, units, Some(destination))
+            }
+        };
+        groups.push((instructions, payload.compute_units_per_recipient.unwrap_or(units)));
+        manifest.push((wallet, amount, token_account));
+    }
+
+    // Greedily pack recipients in order, starting a new transaction whenever
+    // the next one would exceed the packet size or compute limit
+    let mut transactions = Vec::new();
+    let mut transaction_index = Vec::with_capacity(groups.len());
+    let mut priority_fee_total: Option<u64> = None;
+    let mut current: Vec<Instruction> = Vec::new();
+    let mut current_units: u32 = 0;
+    let mut current_encoded = None;
+    for (i, (instructions, units)) in groups.into_iter().enumerate() {
+        if current_encoded.is_some() {
+            let mut candidate = current.clone();
+            candidate.extend(instructions.iter().cloned());
+            let candidate_units = current_units + units;
+            if candidate_units <= MAX_COMPUTE_UNIT_LIMIT {
+                match batch_transaction(&candidate, candidate_units, &fee_payer, &blockhash, payload.micro_lamports_per_cu) {
+                    Ok(Some(encoded)) => {
+                        current = candidate;
+                        current_units = candidate_units;
+                        current_encoded = Some(encoded);
+                        transaction_index.push(transactions.len());
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+                }
+            }
+            if let Some((response, fee)) = current_encoded.take() {
+                transactions.push(response);
+                priority_fee_total = add_fee(priority_fee_total, fee);
+            }
+        }
+
+        match batch_transaction(&instructions, units, &fee_payer, &blockhash, payload.micro_lamports_per_cu) {
+            Ok(Some(encoded)) => current_encoded = Some(encoded),
+            Ok(None) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Recipient {} does not fit in a single transaction", i),
+                );
+            }
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        }
+        current = instructions;
+        current_units = units;
+        transaction_index.push(transactions.len());
+    }
+    if let Some((response, fee)) = current_encoded.take() {
+        transactions.push(response);
+        priority_fee_total = add_fee(priority_fee_total, fee);
+    }
+
+    let manifest = manifest
+        .iter()
+        .zip(transaction_index)
+        .map(|((wallet, amount, token_account), index)| {
+            let mut entry = json!({
+                "recipient": wallet.to_string(),
+                "amount": amount,
+                "transaction_index": index,
+            });
+            if let Some(token_account) = token_account {
+                entry["token_account"] = json!(token_account.to_string());
+            }
+            entry
+        })
+        .collect::<Vec<_>>();
 
     (StatusCode::OK, Json(json!({
         "success": true,
         "data": {
-            "program_id": ix.program_id.to_string(),
-            "accounts": accounts,
-            "instruction_data": data,
+            "transactions": transactions,
+            "manifest": manifest,
+            "total_amount": total,
+            "priority_fee_lamports": priority_fee_total,
         }
     })))
 }
+
+// Compile one batch into an unsigned legacy transaction, or `None` when it
+// exceeds the packet size
+fn batch_transaction(
+    instructions: &[Instruction],
+    units: u32,
+    fee_payer: &Pubkey,
+    blockhash: &Hash,
+    micro_lamports_per_cu: Option<u64>,
+) -> Result<Option<(BuildTransactionResponse, Option<u64>)>, String> {
+    // A priced batch also pins its limit so the fee reflects the estimate
+    let (mut all, priority_fee) = compute_budget_instructions(
+        micro_lamports_per_cu.map(|_| units),
+        micro_lamports_per_cu,
+        instructions.len() as u32,
+    )?;
+    all.extend(instructions.iter().cloned());
+
+    let message = VersionedMessage::Legacy(Message::new_with_blockhash(&all, Some(fee_payer), blockhash));
+    let (bytes, response) = encode_unsigned_transaction(message, &[])
+        .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    if bytes.len() > PACKET_DATA_SIZE {
+        return Ok(None);
+    }
+    Ok(Some((response, priority_fee)))
+}
+
+fn add_fee(total: Option<u64>, fee: Option<u64>) -> Option<u64> {
+    match (total, fee) {
+        (None, fee) => fee,
+        (total, None) => total,
+        (Some(total), Some(fee)) => Some(total.saturating_add(fee)),
+    }
+}
+
+// Lamports to fund a new account with `space` bytes, returned alongside the
+// rent exempt minimum. An explicit amount may not fall below that minimum.
+fn account_lamports(space: u64, lamports: Option<u64>, rent: Option<&RentInput>) -> Result<(u64, u64), String> {
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(format!("`space` must be at most {} bytes", MAX_PERMITTED_DATA_LENGTH));
+    }
+    let rent_exempt_lamports = rent_exempt_minimum(rent, space as usize)?;
+    match lamports {
+        None => Ok((rent_exempt_lamports, rent_exempt_lamports)),
+        Some(lamports) if lamports >= rent_exempt_lamports => Ok((lamports, rent_exempt_lamports)),
+        Some(_) => Err(format!(
+            "`lamports` must be at least the rent exempt minimum of {}",
+            rent_exempt_lamports
+        )),
+    }
+}
+
+fn account_response(
+    ix: Instruction,
+    address: &Pubkey,
+    rent_exempt_lamports: u64,
+    space: u64,
+) -> (StatusCode, Json<serde_json::Value>) {
+    InstructionResponse::new(&[ix])
+        .rent_exempt(rent_exempt_lamports, space as usize)
+        .field("address", address.to_string())
+        .respond()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use super::super::response::response_json;
+
+    async fn batch(body: Value) -> (StatusCode, Value) {
+        let request: BatchSendRequest = serde_json::from_value(body).unwrap();
+        response_json(send_batch(Json(request)).await).await
+    }
+
+    fn token_payout(recipients: usize, compute_units_per_recipient: Option<u32>) -> Value {
+        let recipients = (0..recipients)
+            .map(|_| json!({ "address": Pubkey::new_unique().to_string(), "amount": 1 }))
+            .collect::<Vec<_>>();
+        json!({
+            "from": Pubkey::new_unique().to_string(),
+            "recentBlockhash": Hash::new_unique().to_string(),
+            "mint": Pubkey::new_unique().to_string(),
+            "decimals": 6,
+            "program": "token-2022",
+            "recipients": recipients,
+            "microLamportsPerCu": 1,
+            "computeUnitsPerRecipient": compute_units_per_recipient,
+        })
+    }
+
+    #[tokio::test]
+    async fn packs_by_requested_compute_budget() {
+        // The default Token-2022 estimates fit several recipients per transaction
+        let (status, body) = batch(token_payout(4, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["transactions"].as_array().unwrap().len(), 1);
+
+        // Half the compute limit per recipient leaves room for two
+        let (status, body) = batch(token_payout(4, Some(MAX_COMPUTE_UNIT_LIMIT / 2))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["transactions"].as_array().unwrap().len(), 2);
+        let indexes = body["data"]["manifest"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["transaction_index"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(indexes, vec![0, 0, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_compute_budget() {
+        for units in [0, MAX_COMPUTE_UNIT_LIMIT + 1] {
+            let (status, _) = batch(token_payout(1, Some(units))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+    }
+}